@@ -9,7 +9,7 @@ fn main() {
     console_error_panic_hook::set_once();
     tracing_wasm::set_as_global_default();
 
-    let router = Router::root();
+    let router = Router::root().accessible();
 
     dominator::append_dom(
         &dominator::body(),
@@ -53,8 +53,12 @@ fn main() {
             .child_signal(
                 router
                     .route("quick-start", sections::quickstart)
+                    .title("Quick start")
                     .route("nesting", sections::nesting)
+                    .title("Nesting")
+                    .children(sections::nesting_routes)
                     .route("parameters", sections::parameters)
+                    .title("Parameters")
                     .default("quick-start")
                     .mount()
             )
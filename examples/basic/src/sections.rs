@@ -2,6 +2,6 @@ mod nesting;
 mod parameters;
 mod quickstart;
 
-pub use nesting::nesting;
+pub use nesting::{nesting, nesting_routes};
 pub use parameters::parameters;
 pub use quickstart::quickstart;
@@ -21,12 +21,17 @@ pub fn nesting(router: Router) -> Dom {
             ])
         }))
 
-        .child_signal(
-            router
-                .route("sections/a", || html!("h2", { .text("Section A") }))
-                .route("sections/b", || html!("h2", { .text("Section B") }))
-                .default("sections/a")
-                .mount()
-        )
+        .child_signal(router.mount())
     })
 }
+
+pub fn nesting_routes(router: Router) -> Router {
+    router
+        .route("sections/a", || html!("h2", { .text("Section A") }))
+        .title("Section A")
+        .preserve_scroll()
+        .route("sections/b", || html!("h2", { .text("Section B") }))
+        .title("Section B")
+        .preserve_scroll()
+        .default("sections/a")
+}
@@ -1,6 +1,6 @@
 use dominator::{html, Dom};
 
-const CONTENT: &'static str = r#"
+const CONTENT: &str = r#"
 <h1>Parameters</h1>
 
 <p>
@@ -0,0 +1,125 @@
+use dominator::{html, with_node, Dom};
+use gloo::utils::{body, document};
+use std::cell::RefCell;
+use web_sys::{wasm_bindgen::JsCast, FocusOptions, HtmlElement};
+
+/// The element that receives the focus when a route does not define its own.
+const DEFAULT_FOCUS: &str = "h1";
+
+/// Attribute that marks the element a view has been rendered into.
+const OUTLET: &str = "data-routinator-outlet";
+
+/// Moves the focus to the new view and announces it to screen readers after
+/// each navigation. Stays inactive until it has been enabled.
+#[derive(Default)]
+pub(crate) struct A11y {
+    region: RefCell<Option<HtmlElement>>,
+    focus: RefCell<Option<String>>,
+    /// The outermost view rendered for the current navigation and its depth.
+    view: RefCell<Option<(usize, HtmlElement)>>,
+}
+
+impl A11y {
+    pub fn enable(&self) {
+        let mut region = self.region.borrow_mut();
+
+        if region.is_some() {
+            return;
+        }
+
+        let element: HtmlElement = document().create_element("div").unwrap().unchecked_into();
+
+        element.set_attribute("role", "status").unwrap();
+        element.set_attribute("aria-live", "polite").unwrap();
+        element.set_attribute("aria-atomic", "true").unwrap();
+
+        // Visually hidden, but still read by screen readers
+        element
+            .set_attribute(
+                "style",
+                "position:absolute;width:1px;height:1px;margin:-1px;padding:0;\
+                 overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap;border:0",
+            )
+            .unwrap();
+
+        body().append_child(&element).unwrap();
+        region.replace(element);
+    }
+
+    /// Overrides the focus target for the current navigation.
+    pub fn focus_on(&self, selector: &str) {
+        self.focus.replace(Some(selector.to_string()));
+    }
+
+    pub fn reset(&self) {
+        self.focus.replace(None);
+        self.view.replace(None);
+    }
+
+    /// Wraps a new view of the router at the given depth, so the focus target
+    /// can be looked up within it. Views are left alone while disabled.
+    pub fn wrap(&self, depth: usize, view: Dom) -> Dom {
+        if self.region.borrow().is_none() {
+            return view;
+        }
+
+        html!("div", {
+            .attr(OUTLET, "")
+            .style("display", "contents")
+            .with_node!(element => {
+                .apply(|dom| {
+                    let mut current = self.view.borrow_mut();
+
+                    // Nested views are part of the ones of their parents
+                    if current.as_ref().is_none_or(|(d, _)| depth <= *d) {
+                        current.replace((depth, element));
+                    }
+
+                    dom
+                })
+            })
+            .child(view)
+        })
+    }
+
+    /// Moves the focus and announces the new page. Needs to be called after
+    /// the new views have been rendered.
+    pub fn apply(&self) {
+        let Some(region) = self.region.borrow().clone() else {
+            return;
+        };
+
+        let selector = self
+            .focus
+            .take()
+            .unwrap_or_else(|| DEFAULT_FOCUS.to_string());
+
+        let view = self.view.take().map(|(_, view)| view);
+
+        let target = view
+            .and_then(|v| v.query_selector(&selector).ok().flatten())
+            .or_else(|| document().query_selector(&selector).ok().flatten())
+            .and_then(|e| e.dyn_into::<HtmlElement>().ok());
+
+        let mut message = document().title();
+
+        if let Some(target) = target {
+            // Headings are not focusable by default
+            if !target.has_attribute("tabindex") {
+                target.set_attribute("tabindex", "-1").unwrap();
+            }
+
+            let options = FocusOptions::new();
+            options.set_prevent_scroll(true);
+            target.focus_with_options(&options).ok();
+
+            if message.is_empty() {
+                message = target.text_content().unwrap_or_default();
+            }
+        }
+
+        // Screen readers only pick up changes of the content
+        region.set_text_content(None);
+        region.set_text_content(Some(&message));
+    }
+}
@@ -0,0 +1,174 @@
+use discard::DiscardOnDrop;
+use futures_signals::{cancelable_future, signal::Signal, CancelableFutureHandle};
+use gloo::{
+    events::{EventListener, EventListenerOptions},
+    utils::window,
+};
+use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll, Waker},
+};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{wasm_bindgen::JsCast, BeforeUnloadEvent};
+
+/// The message of the default confirmation dialog.
+const MESSAGE: &str = "Changes you made may not be saved. Leave anyway?";
+
+pub(crate) type Confirm = Rc<dyn Fn(&str) -> bool>;
+
+/// A single block, see [`RouterHandle::block`].
+///
+/// [`RouterHandle::block`]: crate::RouterHandle::block
+struct Block {
+    signal: RefCell<Pin<Box<dyn Signal<Item = bool>>>>,
+    active: Cell<bool>,
+    /// The waker of the task driving the signal. Checking the signal in
+    /// between must not replace it, or the task would no longer be woken.
+    waker: RefCell<Waker>,
+    unload: RefCell<Option<EventListener>>,
+    page: bool,
+}
+
+impl Block {
+    fn poll(&self, cx: &mut Context) -> Poll<()> {
+        let mut signal = self.signal.borrow_mut();
+
+        loop {
+            match signal.as_mut().poll_change(cx) {
+                Poll::Ready(Some(active)) => self.set(active),
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn set(&self, active: bool) {
+        self.active.set(active);
+
+        // Only listen while needed, since the listener keeps browsers from
+        // caching the page for back and forward navigations.
+        if self.page && active != self.unload.borrow().is_some() {
+            self.unload.replace(active.then(|| {
+                EventListener::new_with_options(
+                    &window(),
+                    "beforeunload",
+                    EventListenerOptions::enable_prevent_default(),
+                    |e| {
+                        e.prevent_default();
+                        e.unchecked_ref::<BeforeUnloadEvent>().set_return_value("");
+                    },
+                )
+            }));
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        let waker = self.waker.borrow().clone();
+        let _ = self.poll(&mut Context::from_waker(&waker));
+        self.active.get()
+    }
+}
+
+/// All blocks of a router and the callback that asks whether to leave anyway.
+pub(crate) struct Blocks {
+    blocks: RefCell<Vec<(usize, Rc<Block>)>>,
+    next_id: Cell<usize>,
+    confirm: RefCell<Confirm>,
+    page: bool,
+}
+
+impl Blocks {
+    pub fn new(page: bool) -> Self {
+        let confirm: Confirm = if page {
+            Rc::new(|_| window().confirm_with_message(MESSAGE).unwrap_or(true))
+        } else {
+            Rc::new(|_| false)
+        };
+
+        Self {
+            blocks: Default::default(),
+            next_id: Default::default(),
+            confirm: RefCell::new(confirm),
+            page,
+        }
+    }
+
+    pub fn set_confirm(&self, confirm: Confirm) {
+        self.confirm.replace(confirm);
+    }
+
+    pub fn add(self: &Rc<Self>, signal: impl Signal<Item = bool> + 'static) -> BlockGuard {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        let block = Rc::new(Block {
+            signal: RefCell::new(Box::pin(signal)),
+            active: Cell::new(false),
+            waker: RefCell::new(Waker::noop().clone()),
+            unload: Default::default(),
+            page: self.page,
+        });
+
+        self.blocks.borrow_mut().push((id, block.clone()));
+
+        // Outside of the browser, the signal is only checked when navigating
+        let task = self.page.then(|| {
+            let (handle, future) = cancelable_future(
+                poll_fn(move |cx| {
+                    block.waker.replace(cx.waker().clone());
+                    block.poll(cx)
+                }),
+                || (),
+            );
+
+            spawn_local(future);
+            handle
+        });
+
+        BlockGuard {
+            blocks: Rc::downgrade(self),
+            id,
+            _task: task,
+        }
+    }
+
+    /// Whether a navigation to the given path may happen, asking for
+    /// confirmation while any block is active.
+    pub fn allow(&self, path: &str) -> bool {
+        // Blocks may be dropped while checking them
+        let blocks: Vec<_> = self
+            .blocks
+            .borrow()
+            .iter()
+            .map(|(_, b)| b.clone())
+            .collect();
+
+        if !blocks.iter().any(|b| b.is_active()) {
+            return true;
+        }
+
+        let confirm = self.confirm.borrow().clone();
+        confirm(path)
+    }
+}
+
+/// Keeps a block of [`RouterHandle::block`] in place until it is dropped.
+///
+/// [`RouterHandle::block`]: crate::RouterHandle::block
+#[must_use]
+pub struct BlockGuard {
+    blocks: Weak<Blocks>,
+    id: usize,
+    _task: Option<DiscardOnDrop<CancelableFutureHandle>>,
+}
+
+impl Drop for BlockGuard {
+    fn drop(&mut self) {
+        if let Some(blocks) = self.blocks.upgrade() {
+            blocks.blocks.borrow_mut().retain(|(id, _)| *id != self.id);
+        }
+    }
+}
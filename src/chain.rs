@@ -0,0 +1,198 @@
+use discard::DiscardOnDrop;
+use futures_signals::{
+    cancelable_future,
+    signal::{Mutable, Signal, SignalExt},
+    CancelableFutureHandle,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
+use wasm_bindgen_futures::spawn_local;
+
+use crate::{
+    head::{Head, Metadata},
+    path::Path,
+    router::Params,
+};
+
+/// Typed values attached to a route, at most one per type.
+pub(crate) type Data = HashMap<TypeId, Rc<dyn Any>>;
+
+pub(crate) type Label = Rc<dyn Fn(&Params) -> String>;
+
+/// A single level of the matched route chain, see
+/// [`RouterHandle::breadcrumbs`].
+///
+/// [`RouterHandle::breadcrumbs`]: crate::RouterHandle::breadcrumbs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breadcrumb {
+    pub label: String,
+    /// The path matched until this level, without the base path.
+    pub path: String,
+    /// The parameters matched until this level.
+    pub params: Params,
+}
+
+/// The route matched by a single mounted router.
+pub(crate) struct Level {
+    pub path: Path,
+    pub params: Params,
+    pub label: Option<String>,
+    pub data: Data,
+    /// The latest value of the title signal of the route, if any.
+    pub title: Option<String>,
+    pub meta: Vec<(String, String)>,
+    pub canonical: Option<String>,
+    title_task: Option<DiscardOnDrop<CancelableFutureHandle>>,
+}
+
+impl Level {
+    pub fn new(path: Path, params: Params, label: Option<String>, data: Data) -> Self {
+        Self {
+            path,
+            params,
+            label,
+            data,
+            title: None,
+            meta: Vec::new(),
+            canonical: None,
+            title_task: None,
+        }
+    }
+
+    pub fn breadcrumb(&self) -> Option<Breadcrumb> {
+        Some(Breadcrumb {
+            label: self.label.clone()?,
+            path: self.path.to_string(),
+            params: self.params.clone(),
+        })
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.data.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+}
+
+/// The routes matched by all mounted routers, ordered by their depth within
+/// the route tree. They make up the breadcrumbs and the data of the current
+/// route and, for routers that control the page, the head of the document.
+pub(crate) struct Chain {
+    levels: Mutable<BTreeMap<(usize, usize), Level>>,
+    next_id: Cell<usize>,
+    head: Option<Head>,
+}
+
+impl Chain {
+    pub fn new(page: bool) -> Self {
+        Self {
+            levels: Default::default(),
+            next_id: Default::default(),
+            head: page.then(Head::default),
+        }
+    }
+
+    pub fn set_separator(&self, separator: &str) {
+        if let Some(head) = &self.head {
+            head.set_separator(separator);
+        }
+    }
+
+    /// Reserves a level for a router at the given depth.
+    pub fn register(self: &Rc<Self>, depth: usize) -> Slot {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        Slot {
+            chain: self.clone(),
+            key: (depth, id),
+        }
+    }
+
+    /// Maps the matched levels from the root down.
+    pub fn get<T>(&self, f: impl Fn(&Level) -> Option<T>) -> Vec<T> {
+        self.levels.lock_ref().values().filter_map(f).collect()
+    }
+
+    pub fn signal<T>(&self, f: impl Fn(&Level) -> Option<T>) -> impl Signal<Item = Vec<T>> {
+        self.levels
+            .signal_ref(move |levels| levels.values().filter_map(&f).collect())
+    }
+
+    fn set(self: &Rc<Self>, key: (usize, usize), mut level: Level, metadata: &Metadata) {
+        level.meta = metadata.meta.clone();
+        level.canonical = metadata.canonical.clone();
+
+        if self.head.is_some() {
+            level.title_task = metadata.title.as_ref().map(|title| {
+                let (handle, future) = cancelable_future(
+                    title(&level.params).for_each({
+                        let chain = Rc::downgrade(self);
+                        move |title| {
+                            if let Some(chain) = chain.upgrade() {
+                                if let Some(level) = chain.levels.lock_mut().get_mut(&key) {
+                                    level.title = Some(title);
+                                }
+
+                                chain.sync();
+                            }
+
+                            async {}
+                        }
+                    }),
+                    || (),
+                );
+
+                spawn_local(future);
+                handle
+            });
+        }
+
+        self.levels.lock_mut().insert(key, level);
+        self.sync();
+    }
+
+    fn remove(&self, key: (usize, usize)) {
+        let mut levels = self.levels.lock_mut();
+
+        // Avoids notifying the signals for routers that did not match before
+        if levels.contains_key(&key) {
+            levels.remove(&key);
+            drop(levels);
+            self.sync();
+        }
+    }
+
+    /// Brings the head in line with the matched levels.
+    fn sync(&self) {
+        if let Some(head) = &self.head {
+            head.sync(&self.levels.lock_ref().values().collect::<Vec<_>>());
+        }
+    }
+}
+
+/// The slot of a single router within the [`Chain`]. It is removed once the
+/// router is dropped.
+pub(crate) struct Slot {
+    chain: Rc<Chain>,
+    key: (usize, usize),
+}
+
+impl Slot {
+    /// Fills the slot with the given match and the head metadata of its route.
+    pub fn set(&self, level: Level, metadata: &Metadata) {
+        self.chain.set(self.key, level, metadata);
+    }
+
+    pub fn clear(&self) {
+        self.chain.remove(self.key);
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.chain.remove(self.key);
+    }
+}
@@ -0,0 +1,164 @@
+use futures_signals::signal::Signal;
+use gloo::utils::{document, head};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    pin::Pin,
+    rc::Rc,
+};
+use web_sys::{wasm_bindgen::JsCast, Element};
+
+use crate::{chain::Level, router::Params};
+
+pub(crate) type TitleSignal = Pin<Box<dyn Signal<Item = String>>>;
+pub(crate) type Title = Rc<dyn Fn(&Params) -> TitleSignal>;
+
+/// Attribute that marks the elements within `<head>` that are managed by the
+/// router.
+const MANAGED: &str = "data-routinator";
+
+const CANONICAL: &str = "link[rel=\"canonical\"]";
+
+/// Head metadata declared by a single route.
+#[derive(Clone, Default)]
+pub(crate) struct Metadata {
+    pub title: Option<Title>,
+    pub meta: Vec<(String, String)>,
+    pub canonical: Option<String>,
+}
+
+/// Keeps `document.title` and `<head>` in sync with the metadata of the routes
+/// matched by all mounted routers. Deeper levels take precedence.
+pub(crate) struct Head {
+    separator: RefCell<String>,
+    fallback: RefCell<Option<String>>,
+    /// Elements of the document that are replaced by managed ones while a
+    /// route declares the same tag, by their selector.
+    originals: RefCell<HashMap<String, Element>>,
+}
+
+impl Default for Head {
+    fn default() -> Self {
+        Self {
+            separator: RefCell::new(" · ".to_string()),
+            fallback: Default::default(),
+            originals: Default::default(),
+        }
+    }
+}
+
+impl Head {
+    pub fn set_separator(&self, separator: &str) {
+        self.separator.replace(separator.to_string());
+    }
+
+    /// Applies the metadata of the given levels, from the root down.
+    pub fn sync(&self, levels: &[&Level]) {
+        let document = document();
+
+        let title = levels
+            .iter()
+            .rev()
+            .filter_map(|l| l.title.as_deref())
+            .collect::<Vec<_>>()
+            .join(&self.separator.borrow());
+
+        let fallback = self
+            .fallback
+            .borrow_mut()
+            .get_or_insert_with(|| document.title())
+            .clone();
+
+        document.set_title(if title.is_empty() { &fallback } else { &title });
+
+        let mut meta = BTreeMap::new();
+        let mut canonical = None;
+
+        for level in levels {
+            for (name, content) in &level.meta {
+                meta.insert(name.as_str(), content.as_str());
+            }
+
+            if level.canonical.is_some() {
+                canonical = level.canonical.as_deref();
+            }
+        }
+
+        // Drop all managed elements that are no longer declared
+        let managed = head().query_selector_all(&format!("[{MANAGED}]")).unwrap();
+
+        for i in 0..managed.length() {
+            let Some(element) = managed.get(i).and_then(|n| n.dyn_into::<Element>().ok()) else {
+                continue;
+            };
+
+            let (selector, keep) = match element.tag_name().to_lowercase().as_str() {
+                "meta" => {
+                    let name = element.get_attribute("name").unwrap_or_default();
+                    let keep = meta.contains_key(name.as_str());
+                    (meta_selector(&name), keep)
+                }
+                "link" => (CANONICAL.to_string(), canonical.is_some()),
+                _ => continue,
+            };
+
+            if keep {
+                continue;
+            }
+
+            match self.originals.borrow_mut().remove(&selector) {
+                Some(original) => element.replace_with_with_node_1(&original).unwrap(),
+                None => element.remove(),
+            }
+        }
+
+        for (name, content) in meta {
+            self.managed_element("meta", &meta_selector(name), |e| {
+                e.set_attribute("name", name).unwrap();
+                e.set_attribute("content", content).unwrap();
+            });
+        }
+
+        if let Some(href) = canonical {
+            self.managed_element("link", CANONICAL, |e| {
+                e.set_attribute("rel", "canonical").unwrap();
+                e.set_attribute("href", href).unwrap();
+            });
+        }
+    }
+
+    /// Updates the managed element matching the selector or adds a new one,
+    /// which takes the place of the element of the document until it is no
+    /// longer declared.
+    fn managed_element(&self, tag: &str, selector: &str, update: impl FnOnce(&Element)) {
+        let managed = head()
+            .query_selector(&format!("{selector}[{MANAGED}]"))
+            .ok()
+            .flatten();
+
+        let element = managed.unwrap_or_else(|| {
+            let element = document().create_element(tag).unwrap();
+            element.set_attribute(MANAGED, "").unwrap();
+
+            match head().query_selector(selector).ok().flatten() {
+                Some(original) => {
+                    original.replace_with_with_node_1(&element).unwrap();
+                    self.originals
+                        .borrow_mut()
+                        .insert(selector.to_string(), original);
+                }
+                None => {
+                    head().append_child(&element).unwrap();
+                }
+            }
+
+            element
+        });
+
+        update(&element);
+    }
+}
+
+fn meta_selector(name: &str) -> String {
+    format!("meta[name=\"{name}\"]")
+}
@@ -0,0 +1,309 @@
+use gloo::{events::EventListener, utils::window};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use web_sys::{wasm_bindgen::JsValue, ScrollRestoration};
+
+/// The session history a router reads its location from and navigates in.
+///
+/// Locations are URLs relative to the origin, like `/users/5?tab=1#details`.
+/// Every entry carries a state string that is used by the router itself.
+pub trait History {
+    /// The location of the current entry.
+    fn location(&self) -> String;
+
+    /// The state of the current entry.
+    fn state(&self) -> Option<String>;
+
+    /// Adds a new entry after the current one.
+    fn push(&self, location: &str, state: &str);
+
+    /// Overwrites the current entry.
+    fn replace(&self, location: &str, state: &str);
+
+    /// Moves `delta` entries forward or, for negative values, backward. The
+    /// change is reported to the [`History::subscribe`] callbacks.
+    fn go(&self, delta: isize);
+
+    /// Calls the callback whenever the current entry has been changed from the
+    /// outside, e.g. by the back button of the browser. Changes made through
+    /// [`History::push`] or [`History::replace`] are not reported.
+    fn subscribe(&self, callback: Box<dyn Fn()>) -> Subscription;
+}
+
+/// Stops a [`History::subscribe`] callback from being called when dropped.
+pub struct Subscription(Option<Box<dyn FnOnce()>>);
+
+impl Subscription {
+    pub fn new(unsubscribe: impl FnOnce() + 'static) -> Self {
+        Self(Some(Box::new(unsubscribe)))
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.0.take() {
+            unsubscribe();
+        }
+    }
+}
+
+/// The history of the browser window, which is used by [`Router::root`].
+///
+/// [`Router::root`]: crate::Router::root
+pub struct BrowserHistory {
+    history: web_sys::History,
+}
+
+impl BrowserHistory {
+    pub fn new() -> Self {
+        let history = window().history().unwrap();
+
+        // The browser would restore the scroll offset before the new views
+        // have been rendered, so the router takes care of that itself.
+        history
+            .set_scroll_restoration(ScrollRestoration::Manual)
+            .unwrap();
+
+        Self { history }
+    }
+}
+
+impl Default for BrowserHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl History for BrowserHistory {
+    fn location(&self) -> String {
+        let location = window().location();
+
+        format!(
+            "{}{}{}",
+            location.pathname().unwrap_or_default(),
+            location.search().unwrap_or_default(),
+            location.hash().unwrap_or_default(),
+        )
+    }
+
+    fn state(&self) -> Option<String> {
+        self.history.state().ok().and_then(|s| s.as_string())
+    }
+
+    fn push(&self, location: &str, state: &str) {
+        self.history
+            .push_state_with_url(&JsValue::from_str(state), "", Some(location))
+            .unwrap();
+    }
+
+    fn replace(&self, location: &str, state: &str) {
+        self.history
+            .replace_state_with_url(&JsValue::from_str(state), "", Some(location))
+            .unwrap();
+    }
+
+    fn go(&self, delta: isize) {
+        self.history.go_with_delta(delta as i32).unwrap();
+    }
+
+    fn subscribe(&self, callback: Box<dyn Fn()>) -> Subscription {
+        let listener = EventListener::new(&window(), "popstate", move |_| callback());
+        Subscription::new(move || drop(listener))
+    }
+}
+
+type Listener = Rc<dyn Fn()>;
+
+struct MemoryEntry {
+    location: String,
+    state: Option<String>,
+}
+
+#[derive(Default)]
+struct MemoryInner {
+    entries: RefCell<Vec<MemoryEntry>>,
+    index: Cell<usize>,
+    listeners: RefCell<Vec<(usize, Listener)>>,
+    next_listener: Cell<usize>,
+}
+
+/// A history that keeps its entries in memory instead of touching the URL of
+/// the page. Useful for embedded widgets and for running routers on native
+/// targets, e.g. in unit tests.
+///
+/// Clones share the same entries, so a clone can be kept to navigate after
+/// the original has been passed to [`Router::with_history`].
+///
+/// [`Router::with_history`]: crate::Router::with_history
+#[derive(Clone)]
+pub struct MemoryHistory(Rc<MemoryInner>);
+
+impl MemoryHistory {
+    /// Creates a history with a single entry for the given location.
+    pub fn new(location: &str) -> Self {
+        let inner = MemoryInner::default();
+
+        inner.entries.borrow_mut().push(MemoryEntry {
+            location: location.to_string(),
+            state: None,
+        });
+
+        Self(Rc::new(inner))
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.0.entries.borrow().len()
+    }
+
+    /// Always `false`, since there is at least one entry.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The position of the current entry.
+    pub fn index(&self) -> usize {
+        self.0.index.get()
+    }
+
+    pub fn back(&self) {
+        self.go(-1);
+    }
+
+    pub fn forward(&self) {
+        self.go(1);
+    }
+
+    /// Moves `n` entries forward or, for negative values, backward. Does
+    /// nothing when there is no such entry.
+    pub fn go(&self, n: isize) {
+        let Some(index) = self
+            .0
+            .index
+            .get()
+            .checked_add_signed(n)
+            .filter(|i| *i < self.len())
+        else {
+            return;
+        };
+
+        if index == self.0.index.get() {
+            return;
+        }
+
+        self.0.index.set(index);
+
+        // Listeners may navigate themselves, so the list must not be borrowed
+        // while calling them.
+        let listeners: Vec<_> = self
+            .0
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(_, l)| l.clone())
+            .collect();
+
+        for listener in listeners {
+            listener();
+        }
+    }
+}
+
+impl Default for MemoryHistory {
+    fn default() -> Self {
+        Self::new("/")
+    }
+}
+
+impl History for MemoryHistory {
+    fn location(&self) -> String {
+        self.0.entries.borrow()[self.index()].location.clone()
+    }
+
+    fn state(&self) -> Option<String> {
+        self.0.entries.borrow()[self.index()].state.clone()
+    }
+
+    fn push(&self, location: &str, state: &str) {
+        let index = self.index() + 1;
+        let mut entries = self.0.entries.borrow_mut();
+
+        entries.truncate(index);
+        entries.push(MemoryEntry {
+            location: location.to_string(),
+            state: Some(state.to_string()),
+        });
+
+        self.0.index.set(index);
+    }
+
+    fn replace(&self, location: &str, state: &str) {
+        self.0.entries.borrow_mut()[self.index()] = MemoryEntry {
+            location: location.to_string(),
+            state: Some(state.to_string()),
+        };
+    }
+
+    fn go(&self, delta: isize) {
+        MemoryHistory::go(self, delta);
+    }
+
+    fn subscribe(&self, callback: Box<dyn Fn()>) -> Subscription {
+        let id = self.0.next_listener.get();
+        self.0.next_listener.set(id + 1);
+        self.0.listeners.borrow_mut().push((id, Rc::from(callback)));
+
+        let inner = Rc::downgrade(&self.0);
+
+        Subscription::new(move || {
+            if let Some(inner) = inner.upgrade() {
+                inner.listeners.borrow_mut().retain(|(i, _)| *i != id);
+            }
+        })
+    }
+}
+
+#[test]
+fn test_memory_history() {
+    let history = MemoryHistory::new("/a");
+    let changes = Rc::new(Cell::new(0));
+
+    let subscription = history.subscribe(Box::new({
+        let changes = changes.clone();
+        move || changes.set(changes.get() + 1)
+    }));
+
+    history.push("/b", "1");
+    history.push("/c", "2");
+    assert_eq!(history.location(), "/c");
+    assert_eq!(history.len(), 3);
+
+    history.back();
+    assert_eq!(history.location(), "/b");
+    assert_eq!(history.state().as_deref(), Some("1"));
+
+    history.go(-5);
+    assert_eq!(history.location(), "/b");
+
+    history.go(-1);
+    assert_eq!(history.location(), "/a");
+    assert_eq!(history.state(), None);
+
+    history.forward();
+    history.push("/d", "2");
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.index(), 2);
+
+    history.replace("/e", "3");
+    assert_eq!(history.location(), "/e");
+    assert_eq!(history.len(), 3);
+
+    // Only changes from the outside are reported
+    assert_eq!(changes.get(), 3);
+
+    drop(subscription);
+    history.back();
+    assert_eq!(changes.get(), 3);
+}
@@ -0,0 +1,106 @@
+use dominator::{clone, html, Dom};
+use futures_signals::{
+    signal::{Mutable, Signal, SignalExt},
+    signal_vec::{MutableVec, SignalVecExt},
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use crate::router::{Key, ViewFuture};
+
+/// A view that has been rendered by an outlet.
+struct Kept {
+    key: Key,
+    dom: RefCell<Option<Dom>>,
+    /// When the view has been shown the last time.
+    used: Cell<usize>,
+}
+
+/// Renders the view for each key, but keeps the views of the keys that should
+/// be kept alive around after switching to another key. Inactive views are
+/// hidden, and the least recently shown ones are dropped when there are more
+/// than `limit` of them.
+pub(crate) fn outlet<S, V, K>(keys: S, view: V, limit: usize, is_kept: K) -> Dom
+where
+    S: Signal<Item = Option<Key>> + 'static,
+    V: Fn(&Key) -> ViewFuture + 'static,
+    K: Fn(&Key) -> bool + 'static,
+{
+    let active = Mutable::new(None::<Key>);
+    let views = MutableVec::<Rc<Kept>>::new();
+    let clock = Cell::new(0);
+
+    let task = keys
+        .map_future(clone!(views => move |key| {
+            let cached = key
+                .as_ref()
+                .is_some_and(|k| views.lock_ref().iter().any(|v| v.key == *k));
+
+            // Cached views are shown again without rendering them
+            let view = key.as_ref().filter(|_| !cached).map(&view);
+
+            async move {
+                match view {
+                    Some(view) => (key, Some(view.await)),
+                    None => (key, None),
+                }
+            }
+        }))
+        .for_each(clone!(active, views => move |change| {
+            if let Some((key, dom)) = change {
+                // Needs to be set first, so new views are not hidden at first
+                active.set(key.clone());
+
+                let mut views = views.lock_mut();
+
+                if let (Some(key), Some(dom)) = (&key, dom) {
+                    views.push_cloned(Rc::new(Kept {
+                        key: key.clone(),
+                        dom: RefCell::new(dom),
+                        used: Cell::new(0),
+                    }));
+                }
+
+                clock.set(clock.get() + 1);
+
+                for view in views.iter() {
+                    if Some(&view.key) == key.as_ref() {
+                        view.used.set(clock.get());
+                    }
+                }
+
+                views.retain(|v| Some(&v.key) == key.as_ref() || is_kept(&v.key));
+
+                while views.iter().filter(|v| Some(&v.key) != key.as_ref()).count() > limit {
+                    let Some(oldest) = views
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, v)| Some(&v.key) != key.as_ref())
+                        .min_by_key(|(_, v)| v.used.get())
+                        .map(|(i, _)| i)
+                    else {
+                        break;
+                    };
+
+                    views.remove(oldest);
+                }
+            }
+
+            async {}
+        }));
+
+    html!("div", {
+        .style("display", "contents")
+        .future(task)
+        .children_signal_vec(views.signal_vec_cloned().map(move |view| {
+            let shown = active.signal_ref(clone!(view => move |a| a.as_ref() == Some(&view.key)));
+
+            html!("div", {
+                .style_signal("display", shown.map(|s| if s { "contents" } else { "none" }))
+                .children(view.dom.take())
+            })
+        }))
+    })
+}
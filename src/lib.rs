@@ -1,4 +1,29 @@
+mod a11y;
+mod block;
+mod chain;
+mod head;
+mod history;
+mod keep_alive;
+mod link;
+mod navigation;
 mod path;
+mod preload;
+mod resolve;
 mod router;
+mod scroll;
+mod state;
+mod transition;
+mod tree;
+mod validate;
+mod view_transition;
 
-pub use router::Router;
+pub use block::BlockGuard;
+pub use chain::Breadcrumb;
+pub use history::{BrowserHistory, History, MemoryHistory, Subscription};
+pub use link::LinkOptions;
+pub use navigation::{Cause, Direction, Navigation};
+pub use resolve::{Resolved, RouteMatch};
+pub use router::{Params, Router, RouterHandle};
+pub use tree::{RouteInfo, RouteTree};
+pub use validate::Issue;
+pub use view_transition::shared_element;
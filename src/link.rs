@@ -0,0 +1,335 @@
+use dominator::{events, traits::StaticEvent, with_node, DomBuilder, EventOptions};
+use futures_signals::map_ref;
+use gloo::{
+    events::{EventListener, EventListenerOptions},
+    utils::{document, window},
+};
+use web_sys::{
+    wasm_bindgen::JsCast, Element, Event, EventTarget, FormData, HtmlFormElement, MouseEvent, Url,
+    UrlSearchParams,
+};
+
+use crate::{path::split_url, RouterHandle};
+
+/// Options for styling links, see [`RouterHandle::link_with`].
+#[derive(Debug, Clone)]
+pub struct LinkOptions {
+    active_class: String,
+    exact_active_class: Option<String>,
+    pending_class: Option<String>,
+    aria_current: String,
+    exact: bool,
+}
+
+impl Default for LinkOptions {
+    fn default() -> Self {
+        Self {
+            active_class: "routinator-active".to_string(),
+            exact_active_class: None,
+            pending_class: None,
+            aria_current: "page".to_string(),
+            exact: false,
+        }
+    }
+}
+
+impl LinkOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The class that is added while the link is active, which is
+    /// `routinator-active` by default.
+    pub fn active_class(mut self, class: &str) -> Self {
+        self.active_class = class.to_string();
+        self
+    }
+
+    /// A class that is only added while the current path does not continue
+    /// after the one of the link.
+    pub fn exact_active_class(mut self, class: &str) -> Self {
+        self.exact_active_class = Some(class.to_string());
+        self
+    }
+
+    /// A class that is added while the link is active and the loaders of the
+    /// navigation are still running.
+    pub fn pending_class(mut self, class: &str) -> Self {
+        self.pending_class = Some(class.to_string());
+        self
+    }
+
+    /// The value of the `aria-current` attribute while the current path does
+    /// not continue after the one of the link, which is `page` by default.
+    /// Links that are only active for a parent of the current path get
+    /// `true` instead.
+    pub fn aria_current(mut self, value: &str) -> Self {
+        self.aria_current = value.to_string();
+        self
+    }
+
+    /// Only treats the link as active when the current path does not continue
+    /// after the one of the link.
+    pub fn exact(mut self) -> Self {
+        self.exact = true;
+        self
+    }
+}
+
+/// Attribute that excludes an anchor and all anchors within an element from
+/// being intercepted, see [`Router::intercept_links`].
+///
+/// [`Router::intercept_links`]: crate::Router::intercept_links
+const IGNORE: &str = "data-routinator-ignore";
+
+/// How an element behaves as a link.
+enum Kind {
+    /// Natively supports `href`.
+    Anchor,
+    /// Natively focusable and activated by the keyboard.
+    Button,
+    /// Navigates with its fields as query when submitted.
+    Form,
+    /// Needs to be made focusable and activated by the keyboard.
+    Other,
+}
+
+impl Kind {
+    fn of(element: &Element) -> Self {
+        match element.tag_name().to_ascii_lowercase().as_str() {
+            "a" | "area" => Self::Anchor,
+            "button" => Self::Button,
+            "form" => Self::Form,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Whether a link opens in the current browsing context. Everything else,
+/// like opening it in a new tab or downloading it, is left to the browser.
+fn opens_in_place(element: &Element) -> bool {
+    element
+        .get_attribute("target")
+        .is_none_or(|t| t.is_empty() || t.eq_ignore_ascii_case("_self"))
+        && !element.has_attribute("download")
+}
+
+/// A click with access to the underlying event, which tells whether it has
+/// already been handled elsewhere.
+struct Click(MouseEvent);
+
+impl StaticEvent for Click {
+    const EVENT_TYPE: &'static str = "click";
+
+    fn unchecked_from_event(event: Event) -> Self {
+        Self(event.unchecked_into())
+    }
+}
+
+/// Whether a click on a link should be handled by the router.
+fn is_plain_click(event: &MouseEvent, element: &Element) -> bool {
+    !event.default_prevented()
+        && event.button() == 0
+        && !event.meta_key()
+        && !event.ctrl_key()
+        && !event.shift_key()
+        && !event.alt_key()
+        && opens_in_place(element)
+}
+
+/// Whether a key press activates a link that is not an anchor.
+fn is_activation(event: &events::KeyDown) -> bool {
+    !event.ctrl_key()
+        && !event.shift_key()
+        && !event.alt_key()
+        && matches!(event.key().as_str(), "Enter" | " ")
+}
+
+/// Turns the element into a link to the given target of the handle. Anchors
+/// get an `href`, forms navigate with their fields as query when submitted,
+/// and all other elements are made accessible as links.
+pub(crate) fn link<B>(
+    handle: RouterHandle,
+    path: String,
+    options: LinkOptions,
+    dom: DomBuilder<B>,
+) -> DomBuilder<B>
+where
+    B: AsRef<EventTarget> + AsRef<Element> + Clone,
+{
+    with_node!(dom, node => {
+        .apply(move |dom| {
+            let element: Element = AsRef::<Element>::as_ref(&node).clone();
+
+            match Kind::of(&element) {
+                Kind::Form => submit(dom, element, handle, path),
+                kind => click(dom, element, kind, handle, path, options),
+            }
+        })
+    })
+}
+
+fn click<B>(
+    dom: DomBuilder<B>,
+    element: Element,
+    kind: Kind,
+    handle: RouterHandle,
+    path: String,
+    options: LinkOptions,
+) -> DomBuilder<B>
+where
+    B: AsRef<EventTarget> + AsRef<Element>,
+{
+    let (route, _) = split_url(&path);
+    let LinkOptions {
+        active_class,
+        exact_active_class,
+        pending_class,
+        aria_current,
+        exact,
+    } = options;
+
+    let active = || handle.signal_matches(route, exact);
+
+    let mut dom = dom.class_signal(active_class, active()).attr_signal(
+        "aria-current",
+        map_ref! {
+            let active = active(),
+            let exact = handle.signal_exact(route) =>
+            match (*active, *exact) {
+                (_, true) => Some(aria_current.clone()),
+                (true, false) => Some("true".to_string()),
+                (false, false) => None,
+            }
+        },
+    );
+
+    if let Some(class) = exact_active_class {
+        dom = dom.class_signal(class, handle.signal_exact(route));
+    }
+
+    if let Some(class) = pending_class {
+        dom = dom.class_signal(
+            class,
+            map_ref! {
+                let active = active(),
+                let pending = handle.signal_pending() =>
+                *active && *pending
+            },
+        );
+    }
+
+    let dom = match kind {
+        Kind::Anchor => dom.attr("href", &handle.href(&path)),
+        Kind::Button => dom.attr("role", "link"),
+        _ => {
+            let dom = dom.attr("role", "link");
+
+            let dom = if element.has_attribute("tabindex") {
+                dom
+            } else {
+                dom.attr("tabindex", "0")
+            };
+
+            dom.event_with_options(&EventOptions::preventable(), {
+                let handle = handle.clone();
+                let path = path.clone();
+
+                move |e: events::KeyDown| {
+                    if is_activation(&e) {
+                        e.prevent_default();
+                        handle.goto(&path);
+                    }
+                }
+            })
+        }
+    };
+
+    dom.event_with_options(&EventOptions::preventable(), move |Click(e)| {
+        if is_plain_click(&e, &element) {
+            e.prevent_default();
+            handle.goto(&path);
+        }
+    })
+}
+
+fn submit<B>(
+    dom: DomBuilder<B>,
+    element: Element,
+    handle: RouterHandle,
+    path: String,
+) -> DomBuilder<B>
+where
+    B: AsRef<EventTarget>,
+{
+    dom.event_with_options(&EventOptions::preventable(), move |e: events::Submit| {
+        e.prevent_default();
+
+        let form: &HtmlFormElement = element.unchecked_ref();
+        let query = FormData::new_with_form(form)
+            .and_then(|d| UrlSearchParams::new_with_str_sequence_sequence(&d))
+            .map(|p| String::from(p.to_string()))
+            .unwrap_or_default();
+
+        let (path, _) = split_url(&path);
+
+        if query.is_empty() {
+            handle.goto(path);
+        } else {
+            handle.goto(&format!("{path}?{query}"));
+        }
+    })
+}
+
+/// Listens for clicks on plain anchors anywhere in the document and navigates
+/// to their targets within the app instead of loading a new page.
+pub(crate) fn intercept(handle: RouterHandle) -> EventListener {
+    EventListener::new_with_options(
+        &document(),
+        "click",
+        EventListenerOptions::enable_prevent_default(),
+        move |e| {
+            let e: &MouseEvent = e.unchecked_ref();
+
+            let Some(anchor) = e
+                .target()
+                .and_then(|t| t.dyn_into::<Element>().ok())
+                .and_then(|t| t.closest("a[href], area[href]").ok().flatten())
+            else {
+                return;
+            };
+
+            if !is_plain_click(e, &anchor)
+                || anchor
+                    .closest(&format!("[{IGNORE}]"))
+                    .ok()
+                    .flatten()
+                    .is_some()
+                || anchor
+                    .get_attribute("rel")
+                    .is_some_and(|r| r.split_whitespace().any(|r| r == "external"))
+            {
+                return;
+            }
+
+            let Some(url) = anchor.get_attribute("href").and_then(|href| {
+                let base = document().base_uri().ok().flatten()?;
+                Url::new_with_base(&href, &base).ok()
+            }) else {
+                return;
+            };
+
+            if window().location().origin().ok() != Some(url.origin()) {
+                return;
+            }
+
+            // Links outside of the base path belong to another app
+            let Some(path) = handle.app_path(&url.pathname()) else {
+                return;
+            };
+
+            e.prevent_default();
+            handle.goto(&format!("{path}{}{}", url.search(), url.hash()));
+        },
+    )
+}
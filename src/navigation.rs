@@ -0,0 +1,73 @@
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use std::cell::RefCell;
+
+use crate::router::Params;
+
+/// What caused a [`Navigation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cause {
+    /// A new history entry, e.g. from [`RouterHandle::goto`].
+    ///
+    /// [`RouterHandle::goto`]: crate::RouterHandle::goto
+    Push,
+    /// The current history entry has been overwritten, e.g. from
+    /// [`RouterHandle::replace`].
+    ///
+    /// [`RouterHandle::replace`]: crate::RouterHandle::replace
+    Replace,
+    /// An existing history entry has been activated, e.g. by the back button.
+    Pop,
+    /// A router did not match and redirected to its `default` route.
+    Redirect,
+}
+
+/// Whether a navigation went forward or back in the history, e.g. to let
+/// page transitions slide in the right direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// A new entry or a later one, and also replaced entries.
+    #[default]
+    Forward,
+    /// An earlier entry, e.g. by the back button.
+    Back,
+}
+
+/// A single navigation of the root router and all of its nested routers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Navigation {
+    /// The path before the navigation, without the base path.
+    pub from: String,
+    /// The path after the navigation, without the base path.
+    pub to: String,
+    pub cause: Cause,
+    /// The parameters matched by the new path. Nested levels are only taken
+    /// into account when they have been declared with [`Router::children`].
+    ///
+    /// [`Router::children`]: crate::Router::children
+    pub params: Params,
+}
+
+/// Distributes navigations to all subscribed streams.
+#[derive(Default)]
+pub(crate) struct Navigations {
+    senders: RefCell<Vec<UnboundedSender<Navigation>>>,
+}
+
+impl Navigations {
+    pub fn subscribe(&self) -> UnboundedReceiver<Navigation> {
+        let (sender, receiver) = unbounded();
+        self.senders.borrow_mut().push(sender);
+        receiver
+    }
+
+    pub fn is_observed(&self) -> bool {
+        !self.senders.borrow().is_empty()
+    }
+
+    pub fn emit(&self, navigation: Navigation) {
+        // Streams that have been dropped are removed on the way
+        self.senders
+            .borrow_mut()
+            .retain(|s| s.unbounded_send(navigation.clone()).is_ok());
+    }
+}
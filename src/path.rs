@@ -48,7 +48,7 @@ impl Parser {
     }
 
     pub fn parse(&self, value: &str) -> Result<Vec<Segment>, ()> {
-        Ok(value.split('/').try_fold(Vec::new(), |mut acc, s| {
+        value.split('/').try_fold(Vec::new(), |mut acc, s| {
             match s {
                 "" | "." => {
                     // Empty segments are skipped
@@ -75,7 +75,7 @@ impl Parser {
             }
 
             Ok(acc)
-        })?)
+        })
     }
 }
 
@@ -116,6 +116,13 @@ impl Path {
         self.segments().len()
     }
 
+    /// Removes the given prefix, if this path starts with it.
+    pub fn strip_prefix(&self, prefix: &Path) -> Option<Self> {
+        self.segments()
+            .starts_with(prefix.segments())
+            .then(|| self.skip(prefix.len()))
+    }
+
     #[inline]
     pub fn skip(&self, n: usize) -> Self {
         if n == 0 {
@@ -217,6 +224,22 @@ impl Add for Path {
     }
 }
 
+/// Splits a URL into its path and the rest, i.e. the `?query` and `#fragment`.
+pub fn split_url(url: &str) -> (&str, &str) {
+    match url.find(['?', '#']) {
+        Some(i) => url.split_at(i),
+        None => (url, ""),
+    }
+}
+
+/// The `#fragment` of the rest of a URL as returned by [`split_url`].
+pub fn fragment(suffix: &str) -> Option<&str> {
+    suffix
+        .split_once('#')
+        .map(|(_, f)| f)
+        .filter(|f| !f.is_empty())
+}
+
 // -----------------------------------------------------------------------------
 //                                  R O U T E
 // -----------------------------------------------------------------------------
@@ -230,6 +253,19 @@ impl Route {
         self.0.len()
     }
 
+    #[inline]
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+
+    /// The names of the parameters, in order.
+    pub fn parameters(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|s| match s {
+            Segment::Parameter(p) => Some(p.as_str()),
+            _ => None,
+        })
+    }
+
     pub fn match_path(&self, path: &Path) -> Option<(Path, HashMap<String, String>)> {
         if !path.is_absolute() || self.len() > path.len() {
             return None;
@@ -338,6 +374,35 @@ fn test_parser() {
     );
 }
 
+#[test]
+fn test_strip_prefix() {
+    fn path(p: &str) -> Path {
+        p.parse().unwrap()
+    }
+
+    assert_eq!(
+        path("/tools/planner/users/5").strip_prefix(&path("/tools/planner/")),
+        Some(path("/users/5"))
+    );
+
+    assert_eq!(
+        path("/tools").strip_prefix(&path("/")),
+        Some(path("/tools"))
+    );
+    assert_eq!(path("/other/users").strip_prefix(&path("/tools")), None);
+}
+
+#[test]
+fn test_split_url() {
+    assert_eq!(split_url("users/5"), ("users/5", ""));
+    assert_eq!(split_url("search?q=a#results"), ("search", "?q=a#results"));
+    assert_eq!(split_url("page#top"), ("page", "#top"));
+
+    assert_eq!(fragment("?q=a#results"), Some("results"));
+    assert_eq!(fragment("?q=a#"), None);
+    assert_eq!(fragment("?q=a"), None);
+}
+
 #[test]
 fn test_concatenation() {
     fn path(p: &str) -> Path {
@@ -0,0 +1,151 @@
+use futures_signals::signal::{Mutable, Signal};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    future::{poll_fn, Future},
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    path::{Path, Route},
+    router::{Params, Pending},
+};
+
+type Output = Box<dyn Any>;
+type LoadFuture = Pin<Box<dyn Future<Output = Output>>>;
+
+/// Identifies the match of a route with a loader by its path, its route and
+/// the type of its data.
+pub(crate) type LoadKey = (String, String, TypeId);
+
+/// The loader of a route, with the type of its data erased.
+pub(crate) struct Loader {
+    output: TypeId,
+    load: Box<dyn Fn(Params) -> LoadFuture>,
+}
+
+impl Loader {
+    pub fn new<T, F>(load: impl Fn(Params) -> F + 'static) -> Self
+    where
+        F: Future<Output = T> + 'static,
+        T: 'static,
+    {
+        Self {
+            output: TypeId::of::<T>(),
+            load: Box::new(move |params| {
+                let data = load(params);
+                Box::pin(async move { Box::new(data.await) as Output })
+            }),
+        }
+    }
+
+    pub fn key(&self, path: &Path, route: &Route) -> LoadKey {
+        (path.to_string(), route.to_string(), self.output)
+    }
+
+    /// Runs the loader, which counts as pending until it has finished.
+    pub fn start(&self, params: Params, pending: &Mutable<usize>) -> Rc<Task> {
+        Rc::new(Task {
+            future: RefCell::new(Some((self.load)(params))),
+            output: Default::default(),
+            pending: RefCell::new(Some(Pending::new(pending))),
+        })
+    }
+}
+
+/// A running loader, which can be driven by whoever holds it.
+pub(crate) struct Task {
+    future: RefCell<Option<LoadFuture>>,
+    output: RefCell<Option<Output>>,
+    pending: RefCell<Option<Pending>>,
+}
+
+impl Task {
+    fn poll(&self, cx: &mut Context) -> Poll<()> {
+        let mut future = self.future.borrow_mut();
+
+        let Some(running) = future.as_mut() else {
+            return Poll::Ready(());
+        };
+
+        let Poll::Ready(output) = running.as_mut().poll(cx) else {
+            return Poll::Pending;
+        };
+
+        *future = None;
+        self.output.replace(Some(output));
+        self.pending.take();
+        Poll::Ready(())
+    }
+
+    /// Waits for the loader to finish and takes its data.
+    pub async fn output<T: 'static>(self: Rc<Self>) -> T {
+        poll_fn(|cx| self.poll(cx)).await;
+        *self.output.take().unwrap().downcast().unwrap()
+    }
+}
+
+/// The loaders of the current navigation, which are started as soon as it
+/// begins, so nested levels do not have to wait for the views of their
+/// parents before they start loading.
+#[derive(Default)]
+pub(crate) struct Preloads {
+    tasks: RefCell<HashMap<LoadKey, Rc<Task>>>,
+    /// The matches with a loader of the previous navigation.
+    matched: RefCell<HashSet<LoadKey>>,
+}
+
+impl Preloads {
+    /// Starts the given loaders and drops the ones of the previous navigation
+    /// that have not been taken over. Matches that have not changed keep
+    /// their views, so their loaders do not run again.
+    pub fn start(&self, loaders: Vec<(LoadKey, Rc<Loader>, Params)>, pending: &Mutable<usize>) {
+        let matched = loaders.iter().map(|(key, _, _)| key.clone()).collect();
+        let previous = self.matched.replace(matched);
+
+        let tasks: HashMap<_, _> = loaders
+            .into_iter()
+            .filter(|(key, _, _)| !previous.contains(key))
+            .map(|(key, loader, params)| (key, loader.start(params, pending)))
+            .collect();
+
+        self.tasks.replace(tasks);
+    }
+
+    /// Takes over the loader that has been started for the given match.
+    pub fn take(&self, key: &LoadKey) -> Option<Rc<Task>> {
+        self.tasks.borrow_mut().remove(key)
+    }
+
+    /// Drives all loaders that have not been taken over yet.
+    fn poll(&self, cx: &mut Context) {
+        let tasks: Vec<_> = self.tasks.borrow().values().cloned().collect();
+
+        for task in tasks {
+            let _ = task.poll(cx);
+        }
+    }
+}
+
+/// Drives the started loaders whenever the outlet of the root router is
+/// polled.
+pub(crate) struct Driven<S> {
+    pub preloads: Rc<Preloads>,
+    pub signal: S,
+}
+
+impl<S> Signal for Driven<S>
+where
+    S: Signal + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.preloads.poll(cx);
+        Pin::new(&mut this.signal).poll_change(cx)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::router::Params;
+
+/// The result of [`Router::resolve`].
+///
+/// [`Router::resolve`]: crate::Router::resolve
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolved {
+    /// The resolved path after following all `default` redirects.
+    pub path: String,
+    /// The matched routes from the root router down to the deepest level.
+    pub chain: Vec<RouteMatch>,
+}
+
+impl Resolved {
+    /// The parameters of all levels, where deeper levels take precedence.
+    pub fn params(&self) -> Params {
+        self.chain.iter().flat_map(|m| m.params.clone()).collect()
+    }
+
+    /// The value of a single parameter, see [`Resolved::params`].
+    pub fn param(&self, key: &str) -> Option<String> {
+        self.chain
+            .iter()
+            .rev()
+            .find_map(|m| m.params.get(key))
+            .cloned()
+    }
+}
+
+/// A single level of a [`Resolved`] route chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteMatch {
+    /// The route as it has been registered, e.g. `users/:id`.
+    pub route: String,
+    /// The absolute path matched until this level, e.g. `/users/5`.
+    pub path: String,
+    /// The parameters matched by this level.
+    pub params: Params,
+}
+
+#[test]
+fn test_resolve() {
+    use crate::{MemoryHistory, Router};
+
+    fn settings(router: Router) -> Router {
+        router
+            .route("profile", || None)
+            .route("security", || None)
+            .default("profile")
+    }
+
+    fn user(router: Router) -> Router {
+        router
+            .route("settings", || None)
+            .children(settings)
+            .route("posts/:id", || None)
+    }
+
+    let router = Router::with_history(MemoryHistory::default())
+        .route("users/:id", || None)
+        .children(user)
+        .route("about", || None)
+        .default("about");
+
+    let resolved = router.resolve("/users/5/posts/7?tab=1").unwrap();
+    assert_eq!(resolved.path, "/users/5/posts/7");
+    assert_eq!(
+        resolved
+            .chain
+            .iter()
+            .map(|m| (m.route.as_str(), m.path.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("users/:id", "/users/5"), ("posts/:id", "/users/5/posts/7")]
+    );
+    assert_eq!(resolved.param("id").as_deref(), Some("7"));
+    assert_eq!(resolved.chain[0].params["id"], "5");
+
+    let resolved = router.resolve("/users/5/settings/unknown").unwrap();
+    assert_eq!(resolved.path, "/users/5/settings/profile");
+    assert_eq!(resolved.chain.len(), 3);
+
+    assert_eq!(router.resolve("/nothing").unwrap().path, "/about");
+    assert_eq!(router.resolve("/users/5/nothing"), None);
+}
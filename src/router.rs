@@ -1,18 +1,69 @@
-use dominator::{events, Dom, DomBuilder, EventOptions};
-use futures_signals::signal::{Mutable, Signal, SignalExt};
-use gloo::{events::EventListener, utils::window};
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
-use web_sys::{wasm_bindgen::JsValue, Element, EventTarget};
-
-use crate::path::{Path, Route};
-
-#[inline]
-fn current_path() -> Path {
-    window()
-        .location()
-        .pathname()
-        .unwrap_or_default()
-        .parse()
+use dominator::{Dom, DomBuilder};
+use futures_core::Stream;
+use futures_signals::{
+    signal::{always, Mutable, Signal, SignalExt},
+    signal_vec::SignalVec,
+};
+use gloo::events::EventListener;
+use gloo::timers::future::TimeoutFuture;
+use gloo::utils::{document, window};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    any::TypeId,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Element, EventTarget, Url};
+
+use crate::{
+    a11y::A11y,
+    block::{BlockGuard, Blocks},
+    chain::{Breadcrumb, Chain, Data, Label, Level},
+    head::Metadata,
+    history::{BrowserHistory, History, Subscription},
+    keep_alive,
+    link::{self, link, LinkOptions},
+    navigation::{Cause, Direction, Navigation, Navigations},
+    path::{fragment, split_url, Path, Route},
+    preload::{Driven, Loader, Preloads},
+    resolve::{Resolved, RouteMatch},
+    scroll::{self, Scroll, Target},
+    state::EntryState,
+    transition,
+    tree::{RouteInfo, RouteTree},
+    validate::{self, Issue},
+    view_transition,
+};
+
+/// Parses the path of a history location, without the base path.
+fn location_path(location: &str, base: &Path) -> Path {
+    let path: Path = split_url(location).0.parse().unwrap_or_default();
+
+    path.strip_prefix(base).unwrap_or(path)
+}
+
+/// How many views a router keeps alive unless configured otherwise.
+const DEFAULT_KEEP_ALIVE_LIMIT: usize = 8;
+
+/// Reads the base path from the `<base href>` element of the document, if any.
+/// Like for relative URLs, everything after the last `/` is not part of it.
+fn document_base() -> Path {
+    document()
+        .query_selector("base[href]")
+        .ok()
+        .flatten()
+        .and_then(|_| document().base_uri().ok().flatten())
+        .and_then(|uri| Url::new(&uri).ok())
+        .and_then(|url| {
+            let path = url.pathname();
+            path[..=path.rfind('/')?].parse().ok()
+        })
         .unwrap_or_default()
 }
 
@@ -32,6 +83,8 @@ impl MaybeDom for Option<Dom> {
     }
 }
 
+pub type Params = HashMap<String, String>;
+
 pub trait Handler<A> {
     fn execute(&self, router: &Router) -> Option<Dom>;
 }
@@ -52,18 +105,7 @@ where
     R: MaybeDom,
 {
     fn execute(&self, router: &Router) -> Option<Dom> {
-        (self)(Router {
-            root: router.root.clone(),
-            parent: router
-                .parent
-                .clone()
-                .merge_opt(router.context.borrow().clone()),
-            context: Default::default(),
-            routes: Default::default(),
-            default_path: Default::default(),
-            popstate: None,
-        })
-        .into_option_dom()
+        (self)(router.child()).into_option_dom()
     }
 }
 
@@ -77,6 +119,214 @@ where
     }
 }
 
+pub trait DataHandler<A, T> {
+    fn execute(&self, router: &Router, data: T) -> Option<Dom>;
+}
+
+impl<F, R, T> DataHandler<(), T> for F
+where
+    F: Fn(T) -> R,
+    R: MaybeDom,
+{
+    fn execute(&self, _router: &Router, data: T) -> Option<Dom> {
+        (self)(data).into_option_dom()
+    }
+}
+
+impl<F, R, T> DataHandler<Router, T> for F
+where
+    F: Fn(Router, T) -> R,
+    R: MaybeDom,
+{
+    fn execute(&self, router: &Router, data: T) -> Option<Dom> {
+        (self)(router.child(), data).into_option_dom()
+    }
+}
+
+impl<F, R, T> DataHandler<&Router, T> for F
+where
+    F: Fn(&Router, T) -> R,
+    R: MaybeDom,
+{
+    fn execute(&self, router: &Router, data: T) -> Option<Dom> {
+        (self)(router, data).into_option_dom()
+    }
+}
+
+pub(crate) type ViewFuture = Pin<Box<dyn Future<Output = Option<Dom>>>>;
+type View = Box<dyn Fn(&Rc<Router>, Params) -> ViewFuture>;
+type Children = Rc<dyn Fn(Router) -> Router>;
+
+/// Identifies a view of a router: the index of its route and, for routes that
+/// are rebuilt for each set of parameters, the parameters.
+pub(crate) type Key = (usize, Option<Params>);
+
+struct Entry {
+    route: Route,
+    has_loader: bool,
+    preserve_scroll: bool,
+    keep_alive: bool,
+    focus: Option<String>,
+    head: Metadata,
+    data: Data,
+    label: Option<Label>,
+    children: Option<Children>,
+    loader: Option<Rc<Loader>>,
+    view: View,
+}
+
+/// State shared by the root router and all of its nested routers and handles.
+#[derive(Clone)]
+struct Root {
+    history: Rc<dyn History>,
+    path: Mutable<Path>,
+    entry: Mutable<EntryState>,
+    direction: Mutable<Direction>,
+    pending: Mutable<usize>,
+    scroll: Rc<Scroll>,
+    a11y: Rc<A11y>,
+    chain: Rc<Chain>,
+    base: Rc<RefCell<Path>>,
+    preloads: Rc<Preloads>,
+    /// Whether the router controls the page it is running in, which enables
+    /// scroll restoration and keeps the document title in sync.
+    page: bool,
+    navigation: Rc<Cell<usize>>,
+    navigations: Rc<Navigations>,
+    blocks: Rc<Blocks>,
+    /// Set while a blocked navigation from the outside is being undone.
+    undoing: Rc<Cell<bool>>,
+    /// The mounted root router, used to resolve the parameters of navigations.
+    tree: Rc<RefCell<Weak<Router>>>,
+}
+
+impl Root {
+    /// The current path of the history, without the base path.
+    fn current_path(&self) -> Path {
+        location_path(&self.history.location(), &self.base.borrow())
+    }
+
+    /// Prepends the base path to an absolute path of the app.
+    fn url(&self, path: &Path) -> Path {
+        self.base.borrow().clone() + path.clone()
+    }
+
+    /// Updates the current path and reports the navigation to all streams.
+    fn set_path(&self, path: Path, cause: Cause) {
+        let resolved = self.preload(&path);
+
+        if self.navigations.is_observed() {
+            let params = resolved
+                .map(|chain| {
+                    Resolved {
+                        path: path.to_string(),
+                        chain,
+                    }
+                    .params()
+                })
+                .unwrap_or_default();
+
+            self.navigations.emit(Navigation {
+                from: self.path.lock_ref().to_string(),
+                to: path.to_string(),
+                cause,
+                params,
+            });
+        }
+
+        self.path.set_neq(path);
+    }
+
+    /// Starts the loaders of all levels matching the given path that have
+    /// been declared with [`Router::children`], instead of waiting for the
+    /// views of their parents. Returns the matched levels, if all of them
+    /// match.
+    fn preload(&self, path: &Path) -> Option<Vec<RouteMatch>> {
+        let tree = self.tree.borrow().upgrade()?;
+        let mut loaders = Vec::new();
+
+        let chain = tree.resolve_level(path, &mut |entry, context, params| {
+            // Kept views are usually still around, so they need no loader
+            if let Some(loader) = entry.loader.as_ref().filter(|_| !entry.keep_alive) {
+                let key = loader.key(&context.path, &entry.route);
+                loaders.push((key, loader.clone(), params.clone()));
+            }
+        });
+
+        self.preloads.start(loaders, &self.pending);
+        chain.ok()
+    }
+
+    /// Starts a new navigation and returns its sequence number.
+    fn navigate(&self) -> usize {
+        if self.page {
+            self.scroll.save(self.entry.lock_ref().index);
+        }
+
+        self.scroll.reset();
+        self.a11y.reset();
+        self.navigation.set(self.navigation.get() + 1);
+        self.navigation.get()
+    }
+
+    /// Stores the scroll offset in the state of the current entry before the
+    /// page or the entry is left.
+    fn persist_scroll(&self) {
+        if !self.page {
+            return;
+        }
+
+        let entry = EntryState {
+            scroll: Some(scroll::offset()),
+            ..self.entry.get_cloned()
+        };
+
+        self.history
+            .replace(&self.history.location(), &entry.encode());
+    }
+
+    /// Scrolls and moves the focus as soon as all loaders have finished and
+    /// the new views have been rendered.
+    fn navigated(&self, navigation: usize, target: Target) {
+        if !self.page {
+            return;
+        }
+
+        let root = self.clone();
+
+        spawn_local(async move {
+            TimeoutFuture::new(0).await;
+            root.pending.signal_ref(|n| *n == 0).wait_for(true).await;
+            TimeoutFuture::new(0).await;
+
+            // A newer navigation has taken over in the meantime
+            if root.navigation.get() != navigation {
+                return;
+            }
+
+            let entry = root.entry.get_cloned();
+            root.scroll.apply(target, entry.index, entry.scroll);
+            root.a11y.apply();
+        });
+    }
+}
+
+/// Counts as a running loader for as long as it is alive.
+pub(crate) struct Pending(Mutable<usize>);
+
+impl Pending {
+    pub fn new(pending: &Mutable<usize>) -> Self {
+        *pending.lock_mut() += 1;
+        Self(pending.clone())
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        *self.0.lock_mut() -= 1;
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct Context {
     path: Path,
@@ -89,216 +339,1788 @@ impl Context {
             return self;
         };
 
+        // The path of a match already includes the path of its parent
         Self {
-            path: self.path + rhs.path,
-            params: self
-                .params
-                .into_iter()
-                .chain(rhs.params.into_iter())
-                .collect(),
+            params: self.params.into_iter().chain(rhs.params).collect(),
+            path: rhs.path,
         }
     }
 }
 
+/// What a router has matched, shared with its handles. Updated whenever the
+/// router reacts to a new path, so nested routers follow parameter changes of
+/// their parents without being rebuilt.
+#[derive(Clone, Default)]
+struct Scope {
+    /// Everything matched by the levels above.
+    parent: Rc<RefCell<Context>>,
+    /// The match of the router itself.
+    current: Rc<RefCell<Option<Context>>>,
+    /// The scopes of the levels above, from the root down.
+    ancestors: Rc<Vec<Scope>>,
+}
+
 pub struct Router {
-    root: Mutable<Path>,
-    parent: Context,
-    context: Rc<RefCell<Option<Context>>>,
-    routes: Vec<(Route, Box<dyn Fn(&Router) -> Option<Dom>>)>,
+    root: Root,
+    depth: usize,
+    scope: Scope,
+    routes: Vec<Entry>,
     default_path: Option<Path>,
+    keep_alive_limit: usize,
+    transition: Option<u32>,
+    view_transitions: bool,
+    /// The view that is currently shown.
+    key: RefCell<Option<Key>>,
+    /// The router itself once it has been mounted.
+    mounted: RefCell<Weak<Router>>,
+    /// The router and view this router has been created for, if any.
+    owner: Option<(Weak<Router>, Key)>,
+    #[allow(dead_code)]
+    subscription: Option<Subscription>,
     #[allow(dead_code)]
-    popstate: Option<EventListener>,
+    interceptor: Option<EventListener>,
+    #[allow(dead_code)]
+    pagehide: Option<EventListener>,
 }
 
 impl Router {
+    /// Creates the root router for the history of the browser window.
     pub fn root() -> Self {
-        let root = Mutable::new(current_path());
+        Self::new(Rc::new(BrowserHistory::new()), true)
+    }
+
+    /// Creates a root router that navigates in the given history, e.g. a
+    /// [`MemoryHistory`]. Unlike [`Router::root`], it does not touch the
+    /// scroll offset or the title of the page.
+    ///
+    /// [`MemoryHistory`]: crate::MemoryHistory
+    pub fn with_history(history: impl History + 'static) -> Self {
+        Self::new(Rc::new(history), false)
+    }
+
+    fn new(history: Rc<dyn History>, page: bool) -> Self {
+        let entry = EntryState::parse(history.state()).unwrap_or_default();
+
+        // Entries that have not been created by the router need a state, so
+        // they can be told apart after navigating back.
+        history.replace(&history.location(), &entry.encode());
+
+        let root = Root {
+            path: Mutable::new(location_path(&history.location(), &Path::default())),
+            history: history.clone(),
+            entry: Mutable::new(entry),
+            direction: Default::default(),
+            pending: Default::default(),
+            scroll: Default::default(),
+            a11y: Default::default(),
+            chain: Rc::new(Chain::new(page)),
+            base: Default::default(),
+            preloads: Default::default(),
+            page,
+            navigation: Default::default(),
+            navigations: Default::default(),
+            blocks: Rc::new(Blocks::new(page)),
+            undoing: Default::default(),
+            tree: Default::default(),
+        };
+
+        // Restores the scroll offset of an entry that has been left by
+        // reloading or visiting another site
+        if root.entry.lock_ref().scroll.is_some() {
+            root.navigated(root.navigation.get(), Target::Restore);
+        }
+
+        let pagehide = page.then(|| {
+            let root = root.clone();
+            EventListener::new(&window(), "pagehide", move |_| root.persist_scroll())
+        });
 
         Self {
             root: root.clone(),
-            parent: Default::default(),
-            context: Default::default(),
+            depth: 0,
+            scope: Default::default(),
             routes: Default::default(),
             default_path: Default::default(),
-            popstate: Some(EventListener::new(&window(), "popstate", move |_| {
-                root.set_neq(current_path());
-            })),
+            keep_alive_limit: DEFAULT_KEEP_ALIVE_LIMIT,
+            transition: None,
+            view_transitions: false,
+            key: Default::default(),
+            mounted: Default::default(),
+            owner: None,
+            subscription: Some(history.subscribe(Box::new(move || {
+                if root.undoing.replace(false) {
+                    return;
+                }
+
+                let index = root.entry.lock_ref().index;
+
+                // Entries the browser has created on its own, e.g. for links to
+                // a fragment, always come right after the current one.
+                let (entry, created) = match EntryState::parse(root.history.state()) {
+                    Some(entry) => (entry, false),
+                    None => (
+                        EntryState {
+                            index: index + 1,
+                            ..Default::default()
+                        },
+                        true,
+                    ),
+                };
+
+                // The history has already moved, so it needs to be moved back
+                if !root.blocks.allow(&root.current_path().to_string()) {
+                    if entry.index == index {
+                        // Only the URL of the entry has changed
+                        root.history.replace(
+                            &root.url(&root.path.lock_ref()).to_string(),
+                            &root.entry.lock_ref().encode(),
+                        );
+                    } else {
+                        root.undoing.set(true);
+                        root.history.go(index as isize - entry.index as isize);
+                    }
+
+                    return;
+                }
+
+                let navigation = root.navigate();
+                let location = root.history.location();
+
+                let target = if created {
+                    root.scroll.truncate(entry.index);
+                    root.history.replace(&location, &entry.encode());
+
+                    match fragment(split_url(&location).1) {
+                        Some(f) => Target::Fragment(f.to_string()),
+                        None => Target::Top,
+                    }
+                } else {
+                    Target::Restore
+                };
+
+                root.direction.set_neq(if entry.index < index {
+                    Direction::Back
+                } else {
+                    Direction::Forward
+                });
+
+                root.entry.set(entry);
+                root.set_path(root.current_path(), Cause::Pop);
+                root.navigated(navigation, target);
+            }))),
+            interceptor: None,
+            pagehide,
         }
     }
 
-    pub fn route<A>(mut self, path: &str, handler: impl Handler<A> + 'static) -> Self {
-        self.routes.push((
-            path.parse().unwrap(),
-            Box::new(move |router| handler.execute(router)),
-        ));
-
+    /// Replaces the dialog that asks whether to leave while a navigation is
+    /// blocked, see [`RouterHandle::block`]. The callback receives the target
+    /// path and returns whether to navigate anyway. By default, the browser
+    /// asks with `window.confirm`, and routers created with
+    /// [`Router::with_history`] stay where they are.
+    pub fn confirm_with(self, confirm: impl Fn(&str) -> bool + 'static) -> Self {
+        self.root.blocks.set_confirm(Rc::new(confirm));
         self
     }
 
-    pub fn default(mut self, path: &str) -> Self {
-        self.default_path = Some(path.parse().unwrap());
+    /// A stream of all navigations from now on, see
+    /// [`RouterHandle::navigations`].
+    pub fn navigations(&self) -> impl Stream<Item = Navigation> {
+        self.handle().navigations()
+    }
+
+    /// Serves the app under the given path prefix. It is stripped from the
+    /// location before matching and prepended to all generated URLs.
+    pub fn base(self, base: &str) -> Self {
+        self.with_base(base.parse().unwrap())
+    }
+
+    /// Like [`Router::base`], but reads the prefix from the `<base href>`
+    /// element of the document, e.g. `/tools/planner/` for
+    /// `<base href="/tools/planner/index.html">`.
+    pub fn base_from_document(self) -> Self {
+        self.with_base(document_base())
+    }
+
+    fn with_base(self, base: Path) -> Self {
+        self.root.base.replace(base);
+        self.root.path.set_neq(self.root.current_path());
         self
     }
 
-    pub fn handle(&self) -> RouterHandle {
-        RouterHandle {
-            root: self.root.clone(),
-            parent: self.parent.clone(),
-            current: self.context.clone(),
+    /// Handles clicks on all plain `<a href>` elements of the document that
+    /// point to the same origin and lie within the base path, so they don't
+    /// need to be created through [`Router::link`]. Clicks with modifier keys,
+    /// links with a `target`, `download` or `rel="external"` attribute and
+    /// links within an element with a `data-routinator-ignore` attribute are
+    /// left to the browser. Only has an effect for [`Router::root`].
+    pub fn intercept_links(mut self) -> Self {
+        if self.root.page && self.depth == 0 {
+            self.interceptor = Some(link::intercept(self.handle()));
         }
+
+        self
     }
 
-    pub fn mount(mut self) -> impl Signal<Item = Option<Dom>> {
-        let routes: Vec<Route> = self.routes.iter().map(|(r, _)| r.clone()).collect();
-        let default_path = self.default_path.take();
+    fn child(&self) -> Self {
+        let mut child = self.nested(self.scope.current.borrow().clone());
 
-        self.root
-            .signal_cloned()
-            .map({
-                let handle = self.handle();
-                move |p| {
-                    for (i, r) in routes.iter().enumerate() {
-                        let test = p.skip(handle.parent.path.len());
-                        if let Some((p, par)) = r.match_path(&test) {
-                            handle.current.replace(Some(Context {
-                                path: handle.parent.path.clone() + p,
-                                params: par,
-                            }));
-                            return Some(i);
-                        }
-                    }
+        let Some(key) = self.key.borrow().clone() else {
+            return child;
+        };
 
-                    handle.current.replace(None);
+        let children = self.routes[key.0].children.clone();
+        child.owner = Some((self.mounted.borrow().clone(), key));
 
-                    if let Some(p) = &default_path {
-                        handle.replace(&p.to_string());
-                    }
+        match children {
+            Some(children) => children(child),
+            None => child,
+        }
+    }
 
-                    None
-                }
-            })
-            .dedupe()
-            .map(move |i| i.and_then(|i| (self.routes.get(i).unwrap().1)(&self)))
+    /// Creates a router for the level below the given match of this router.
+    fn nested(&self, context: Option<Context>) -> Self {
+        Self {
+            root: self.root.clone(),
+            depth: self.depth + 1,
+            scope: Scope {
+                parent: Rc::new(RefCell::new(
+                    self.scope.parent.borrow().clone().merge_opt(context),
+                )),
+                current: Default::default(),
+                ancestors: Rc::new(
+                    self.scope
+                        .ancestors
+                        .iter()
+                        .cloned()
+                        .chain([self.scope.clone()])
+                        .collect(),
+                ),
+            },
+            routes: Default::default(),
+            default_path: Default::default(),
+            keep_alive_limit: DEFAULT_KEEP_ALIVE_LIMIT,
+            transition: None,
+            view_transitions: false,
+            key: Default::default(),
+            mounted: Default::default(),
+            owner: None,
+            subscription: None,
+            interceptor: None,
+            pagehide: None,
+        }
     }
 
-    pub fn link<B>(&self, path: &str) -> impl FnOnce(DomBuilder<B>) -> DomBuilder<B> + '_
+    pub fn route<A>(mut self, path: &str, handler: impl Handler<A> + 'static) -> Self {
+        self.routes.push(Entry {
+            route: path.parse().unwrap(),
+            has_loader: false,
+            preserve_scroll: false,
+            keep_alive: false,
+            focus: None,
+            head: Default::default(),
+            data: Default::default(),
+            label: None,
+            children: None,
+            loader: None,
+            view: Box::new(move |router, _| {
+                let dom = handler.execute(router);
+                Box::pin(async move { dom })
+            }),
+        });
+
+        self
+    }
+
+    /// Registers a route whose handler receives the result of an async loader.
+    ///
+    /// The loader gets the parameters of the whole route tree until this point
+    /// and runs whenever the route or its parameters change. The previous view
+    /// stays mounted until the loader has finished. The loaders of all levels
+    /// declared with [`Router::children`] start at the same time when the
+    /// navigation begins, instead of waiting for the views of their parents.
+    pub fn route_with_loader<A, T, F>(
+        mut self,
+        path: &str,
+        loader: impl Fn(Params) -> F + 'static,
+        handler: impl DataHandler<A, T> + 'static,
+    ) -> Self
     where
-        B: AsRef<EventTarget> + AsRef<Element>,
+        F: Future<Output = T> + 'static,
+        T: 'static,
     {
-        let path = path.to_string();
-        let handle = self.handle();
+        let handler = Rc::new(handler);
+        let loader = Rc::new(Loader::new(loader));
+        let route: Route = path.parse().unwrap();
 
-        // TODO: Only set href for actual <a> nodes?
-        move |dom| {
-            dom.attr("href", &handle.link_target(&path).to_string())
-                .class_signal("routinator-active", self.signal_active(&path))
-                .event_with_options(&EventOptions::preventable(), move |e: events::Click| {
-                    if !e.ctrl_key() && !e.shift_key() {
-                        e.prevent_default();
-                        handle.goto(&path);
-                    }
+        self.routes.push(Entry {
+            route: route.clone(),
+            has_loader: true,
+            preserve_scroll: false,
+            keep_alive: false,
+            focus: None,
+            head: Default::default(),
+            data: Default::default(),
+            label: None,
+            children: None,
+            loader: Some(loader.clone()),
+            view: Box::new(move |router, params| {
+                let path = router
+                    .scope
+                    .current
+                    .borrow()
+                    .as_ref()
+                    .map(|c| c.path.clone());
+
+                // The loader may have been started along with the navigation
+                let task = path
+                    .and_then(|p| router.root.preloads.take(&loader.key(&p, &route)))
+                    .unwrap_or_else(|| loader.start(params, &router.root.pending));
+
+                let router = router.clone();
+                let handler = handler.clone();
+
+                Box::pin(async move {
+                    let data = task.output::<T>().await;
+                    handler.execute(&router, data)
                 })
-        }
-    }
+            }),
+        });
 
-    pub fn param(&self, key: &str) -> Option<String> {
-        self.context
-            .borrow()
-            .as_ref()
-            .and_then(|ctx| ctx.params.get(key))
-            .or_else(|| self.parent.params.get(key))
-            .cloned()
+        self
     }
 
-    pub fn signal_active(&self, path: &str) -> impl Signal<Item = bool> {
-        let handle = self.handle();
-        let route: Route = path.parse().unwrap();
+    /// Keeps the scroll offset when switching to the most recently registered
+    /// route from another route of this router, e.g. for tabs.
+    pub fn preserve_scroll(mut self) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry.preserve_scroll = true;
+        }
 
-        self.root.signal_ref(move |p| {
-            route
-                .match_path(&p.skip(handle.parent.path.len()))
-                .is_some()
-        })
+        self
     }
-}
 
-#[derive(Clone)]
-pub struct RouterHandle {
-    root: Mutable<Path>,
-    parent: Context,
-    current: Rc<RefCell<Option<Context>>>,
-}
+    /// Keeps the view of the most recently registered route alive after
+    /// navigating away from it, so it is shown again with its state intact,
+    /// e.g. form input, when coming back. Views are kept per set of parameters
+    /// and hidden with `display: none` while inactive. Their loaders don't run
+    /// again, and nested routers are paused until the view is shown again.
+    pub fn keep_alive(mut self) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry.keep_alive = true;
+        }
 
-impl RouterHandle {
-    pub fn goto(&self, target: &str) {
-        let target = self.link_target(target);
+        self
+    }
 
-        // This does not trigger a popstate event, so we need to update the URL
-        // afterwards to keep everything in sync.
-        window()
-            .history()
-            .unwrap()
-            .push_state_with_url(&JsValue::null(), "", Some(&target.to_string()))
-            .unwrap();
+    /// The maximum number of hidden views that are kept alive by this router,
+    /// which is 8 by default. The least recently shown view is dropped first.
+    pub fn keep_alive_limit(mut self, limit: usize) -> Self {
+        self.keep_alive_limit = limit;
+        self
+    }
 
-        self.root.set_neq(target);
+    /// Animates switching between the views of this router. The leaving view
+    /// stays mounted for the given duration, while it has the class
+    /// `routinator-leave` and the new view has the class `routinator-enter`.
+    /// Both views also have the class `routinator-forward` or
+    /// `routinator-back`, depending on the [`RouterHandle::direction`] of the
+    /// navigation. Each view is wrapped in an element with `display: contents`.
+    /// Has no effect on routers with views that are kept alive.
+    pub fn transition(mut self, duration: Duration) -> Self {
+        self.transition = Some(duration.as_millis().try_into().unwrap_or(u32::MAX));
+        self
     }
 
-    pub fn replace(&self, target: &str) {
-        let target = self.link_target(target);
+    /// Switches between the views of this router within a view transition of
+    /// the document, which cross-fades them by default. Elements marked with
+    /// [`shared_element`] are animated between their old and new positions.
+    /// Browsers without support for view transitions switch instantly. Has no
+    /// effect on routers with a [`Router::transition`] or with views that are
+    /// kept alive.
+    ///
+    /// [`shared_element`]: crate::shared_element
+    pub fn view_transitions(mut self) -> Self {
+        self.view_transitions = true;
+        self
+    }
 
-        // This does not trigger a popstate event, so we need to update the URL
-        // afterwards to keep everything in sync.
-        window()
-            .history()
-            .unwrap()
-            .replace_state_with_url(&JsValue::null(), "", Some(&target.to_string()))
-            .unwrap();
+    /// Moves the focus to the first element matching the given selector when
+    /// navigating to the most recently registered route. Only has an effect
+    /// when [`Router::accessible`] is enabled.
+    pub fn focus(mut self, selector: &str) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry.focus = Some(selector.to_string());
+        }
 
-        self.root.set_neq(target);
+        self
     }
 
-    pub fn param(&self, key: &str) -> Option<String> {
-        self.current
-            .borrow()
-            .as_ref()
-            .and_then(|ctx| ctx.params.get(key))
-            .or_else(|| self.parent.params.get(key))
-            .cloned()
+    /// Moves the focus to the new view and announces the new page title
+    /// through a live region after each navigation. By default, the first
+    /// `<h1>` element of the new view receives the focus, or the first one of
+    /// the document if the view has none. To tell the new view apart, views
+    /// are wrapped in an element with `display: contents`.
+    pub fn accessible(self) -> Self {
+        self.root.a11y.enable();
+        self
     }
 
-    pub fn link<B>(&self, path: &str) -> impl FnOnce(DomBuilder<B>) -> DomBuilder<B> + '_
+    /// Sets the document title for the most recently registered route. Titles
+    /// of nested routes are prepended to the ones of their parents.
+    pub fn title(self, title: &str) -> Self {
+        let title = title.to_string();
+        self.title_signal(move |_| always(title.clone()))
+    }
+
+    /// Like [`Router::title`], but builds the title from the parameters of the
+    /// whole route tree until this point.
+    pub fn title_with(self, title: impl Fn(&Params) -> String + 'static) -> Self {
+        self.title_signal(move |params| always(title(params)))
+    }
+
+    /// Like [`Router::title`], but keeps the title in sync with a signal.
+    pub fn title_signal<S>(mut self, title: impl Fn(&Params) -> S + 'static) -> Self
     where
-        B: AsRef<EventTarget> + AsRef<Element>,
+        S: Signal<Item = String> + 'static,
     {
-        let path = path.to_string();
-        let handle = self.clone();
+        if let Some(entry) = self.routes.last_mut() {
+            entry.head.title = Some(Rc::new(move |params| Box::pin(title(params))));
+        }
 
-        // TODO: Only set href for actual <a> nodes?
-        move |dom| {
-            dom.attr("href", &handle.link_target(&path).to_string())
-                .class_signal("routinator-active", self.signal_active(&path))
-                .event_with_options(&EventOptions::preventable(), move |e: events::Click| {
-                    if !e.ctrl_key() && !e.shift_key() {
-                        e.prevent_default();
-                        handle.goto(&path);
-                    }
-                })
+        self
+    }
+
+    /// Sets the separator between the titles of nested routes, which is " · "
+    /// by default.
+    pub fn title_separator(self, separator: &str) -> Self {
+        self.root.chain.set_separator(separator);
+        self
+    }
+
+    /// Adds a `<meta name="..." content="...">` tag to `<head>` while the most
+    /// recently registered route is active. Nested routes override the tags of
+    /// their parents.
+    pub fn meta(mut self, name: &str, content: &str) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry
+                .head
+                .meta
+                .push((name.to_string(), content.to_string()));
         }
+
+        self
     }
 
-    pub fn signal_active(&self, path: &str) -> impl Signal<Item = bool> {
-        let handle = self.clone();
-        let route: Route = path.parse().unwrap();
+    /// Adds a `<link rel="canonical">` tag to `<head>` while the most recently
+    /// registered route is active.
+    pub fn canonical(mut self, href: &str) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry.head.canonical = Some(href.to_string());
+        }
 
-        self.root.signal_ref(move |p| {
-            route
-                .match_path(&p.skip(handle.parent.path.len()))
-                .is_some()
-        })
+        self
     }
 
-    fn link_target(&self, target: &str) -> Path {
-        self.parent.path.clone() + target.parse().unwrap()
+    /// Attaches a typed value to the most recently registered route, e.g. the
+    /// role that is required to see it or the layout it should be shown in.
+    /// A route holds one value per type. See [`RouterHandle::signal_data`].
+    pub fn data<T: 'static>(mut self, value: T) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry.data.insert(TypeId::of::<T>(), Rc::new(value));
+        }
+
+        self
+    }
+
+    /// Labels the most recently registered route in the
+    /// [`RouterHandle::breadcrumbs`].
+    pub fn breadcrumb(self, label: &str) -> Self {
+        let label = label.to_string();
+        self.breadcrumb_with(move |_| label.clone())
+    }
+
+    /// Like [`Router::breadcrumb`], but builds the label from the parameters
+    /// of the whole route tree until this point.
+    pub fn breadcrumb_with(mut self, label: impl Fn(&Params) -> String + 'static) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry.label = Some(Rc::new(label));
+        }
+
+        self
+    }
+
+    /// Declares the nested routes of the most recently registered route, so
+    /// they are known without mounting it, e.g. by [`Router::resolve`], and
+    /// their loaders start along with the navigation. The router passed to the
+    /// handler already has these routes, so it only needs to be mounted.
+    pub fn children(mut self, children: impl Fn(Router) -> Router + 'static) -> Self {
+        if let Some(entry) = self.routes.last_mut() {
+            entry.children = Some(Rc::new(children));
+        }
+
+        self
     }
+
+    pub fn default(mut self, path: &str) -> Self {
+        self.default_path = Some(path.parse().unwrap());
+        self
+    }
+
+    /// A handle for the router one level up, see [`RouterHandle::parent`].
+    pub fn parent(&self) -> Option<RouterHandle> {
+        self.handle().parent()
+    }
+
+    /// A handle for the root router, see [`RouterHandle::root_handle`].
+    pub fn root_handle(&self) -> RouterHandle {
+        self.handle().root_handle()
+    }
+
+    pub fn handle(&self) -> RouterHandle {
+        RouterHandle {
+            root: self.root.clone(),
+            scope: self.scope.clone(),
+        }
+    }
+
+    pub fn mount(self) -> impl Signal<Item = Option<Dom>> {
+        let router = Rc::new(self);
+
+        if router.depth == 0 {
+            router.root.tree.replace(Rc::downgrade(&router));
+            router.root.preload(&router.root.path.get_cloned());
+        }
+
+        router.mounted.replace(Rc::downgrade(&router));
+
+        #[cfg(debug_assertions)]
+        if router.depth == 0 {
+            for issue in router.validate() {
+                tracing::warn!("{issue}");
+            }
+        }
+
+        let keys = router
+            .root
+            .path
+            .signal_cloned()
+            .map({
+                let router = router.clone();
+                let handle = router.handle();
+                let slot = router.root.chain.register(router.depth);
+                let mut previous = None;
+                let mut last = None;
+
+                move |p| {
+                    // Routers within hidden views must not react to paths
+                    // that are meant for other views.
+                    let Some(parent) = router.parent_at(&p) else {
+                        previous = None;
+                        slot.clear();
+                        return last.clone();
+                    };
+
+                    handle.scope.parent.replace(parent.clone());
+
+                    if let Some((i, context)) = router.find(&p) {
+                        let entry = &router.routes[i];
+                        let params = parent.merge_opt(Some(context.clone())).params;
+
+                        if previous != Some((i, params.clone())) {
+                            let level = Level::new(
+                                context.path.clone(),
+                                params.clone(),
+                                entry.label.as_ref().map(|l| l(&params)),
+                                entry.data.clone(),
+                            );
+
+                            slot.set(level, &entry.head);
+                        }
+
+                        if entry.preserve_scroll && previous.is_some() {
+                            handle.root.scroll.preserve();
+                        }
+
+                        if let Some(selector) = &entry.focus {
+                            if previous.as_ref().map(|(p, _)| *p) != Some(i) {
+                                handle.root.a11y.focus_on(selector);
+                            }
+                        }
+
+                        previous = Some((i, params.clone()));
+                        handle.scope.current.replace(Some(context));
+
+                        let key = router.key(i, params);
+                        router.key.replace(Some(key.clone()));
+                        last = Some(key);
+                        return last.clone();
+                    }
+
+                    previous = None;
+                    last = None;
+                    slot.clear();
+                    handle.scope.current.replace(None);
+                    router.key.replace(None);
+
+                    if let Some(p) = &router.default_path {
+                        handle.redirect(&p.to_string());
+                    }
+
+                    None
+                }
+            })
+            .dedupe_cloned();
+
+        let view = {
+            let router = router.clone();
+            move |(i, params): &Key| -> ViewFuture {
+                let view = (router.routes[*i].view)(&router, params.clone().unwrap_or_default());
+                let a11y = router.root.a11y.clone();
+                let depth = router.depth;
+
+                Box::pin(async move { view.await.map(|dom| a11y.wrap(depth, dom)) })
+            }
+        };
+
+        let outlet = if router.routes.iter().any(|e| e.keep_alive) {
+            let outlet = keep_alive::outlet(keys, view, router.keep_alive_limit, {
+                let router = router.clone();
+                move |(i, _): &Key| router.routes[*i].keep_alive
+            });
+
+            always(Some(outlet)).boxed_local()
+        } else {
+            let views = keys
+                .map_future(move |key| {
+                    let view = key.as_ref().map(&view);
+
+                    async move {
+                        match view {
+                            Some(view) => view.await,
+                            None => None,
+                        }
+                    }
+                })
+                .map(Option::flatten);
+
+            match router.transition {
+                Some(duration) => {
+                    let direction = router.root.direction.clone();
+                    always(Some(transition::outlet(views, direction, duration))).boxed_local()
+                }
+                None if router.view_transitions => {
+                    let pending = router.root.pending.clone();
+                    always(Some(view_transition::outlet(views, pending))).boxed_local()
+                }
+                None => views.boxed_local(),
+            }
+        };
+
+        if router.depth == 0 {
+            Driven {
+                preloads: router.root.preloads.clone(),
+                signal: outlet,
+            }
+            .boxed_local()
+        } else {
+            outlet
+        }
+    }
+
+    /// Identifies the view for the given match. Routes with a loader need to
+    /// be reloaded when their parameters change, kept views are cached per set
+    /// of parameters, and all others are only rebuilt when the matched route
+    /// changes.
+    fn key(&self, i: usize, params: Params) -> Key {
+        let entry = &self.routes[i];
+        (i, (entry.has_loader || entry.keep_alive).then_some(params))
+    }
+
+    /// Everything the levels above match for the given path. Returns `None`
+    /// if the view this router has been created for is not shown for it, i.e.
+    /// if any of its ancestors matches something else.
+    fn parent_at(&self, path: &Path) -> Option<Context> {
+        let Some((owner, key)) = &self.owner else {
+            return Some(self.scope.parent.borrow().clone());
+        };
+
+        let owner = owner.upgrade()?;
+        let parent = owner.parent_at(path)?;
+        let (i, context) = owner.find_in(&parent, path)?;
+        let parent = parent.merge_opt(Some(context));
+
+        (owner.key(i, parent.params.clone()) == *key).then_some(parent)
+    }
+
+    /// Finds the first route matching the given absolute path.
+    fn find(&self, path: &Path) -> Option<(usize, Context)> {
+        self.find_in(&self.scope.parent.borrow(), path)
+    }
+
+    /// Like [`Router::find`], but below the given match of the levels above.
+    fn find_in(&self, parent: &Context, path: &Path) -> Option<(usize, Context)> {
+        let test = path.skip(parent.path.len());
+
+        self.routes.iter().enumerate().find_map(|(i, entry)| {
+            entry.route.match_path(&test).map(|(p, params)| {
+                (
+                    i,
+                    Context {
+                        path: parent.path.clone() + p,
+                        params,
+                    },
+                )
+            })
+        })
+    }
+
+    /// Runs the same matching as [`Router::mount`] for the given URL, but
+    /// without touching the history or building any views. Nested routes are
+    /// only taken into account when they have been declared with
+    /// [`Router::children`].
+    ///
+    /// Returns `None` if any level of the route tree does not match, even
+    /// after following its `default` redirect.
+    pub fn resolve(&self, url: &str) -> Option<Resolved> {
+        // Guards against `default` routes redirecting to each other
+        const MAX_REDIRECTS: usize = 16;
+
+        let mut path = location_path(url, &self.root.base.borrow());
+
+        for _ in 0..=MAX_REDIRECTS {
+            match self.resolve_level(&path, &mut |_, _, _| ()) {
+                Ok(chain) => {
+                    return Some(Resolved {
+                        path: path.to_string(),
+                        chain,
+                    })
+                }
+                Err(Some(redirect)) => path = redirect,
+                Err(None) => return None,
+            }
+        }
+
+        None
+    }
+
+    /// Describes all routes of this router and the nested routes declared with
+    /// [`Router::children`], e.g. to generate a sitemap or to check links.
+    pub fn tree(&self) -> RouteTree {
+        self.describe(&self.scope.parent.borrow().path.to_string())
+    }
+
+    fn describe(&self, prefix: &str) -> RouteTree {
+        let routes = self
+            .routes
+            .iter()
+            .map(|entry| {
+                let route = entry.route.to_string();
+                let path = validate::join(prefix, &route);
+
+                RouteInfo {
+                    params: entry.route.parameters().map(str::to_string).collect(),
+                    loader: entry.has_loader,
+                    keep_alive: entry.keep_alive,
+                    children: entry
+                        .children
+                        .as_ref()
+                        .map(|children| children(self.nested(None)).describe(&path)),
+                    route,
+                    path,
+                }
+            })
+            .collect();
+
+        RouteTree {
+            default: self
+                .default_path
+                .as_ref()
+                .map(|d| d.to_string().trim_start_matches('/').to_string()),
+            routes,
+        }
+    }
+
+    /// Checks this router and the nested routes declared with
+    /// [`Router::children`] for routes that can never match, duplicates,
+    /// parameters hiding the ones of their parents and `default` routes that
+    /// match nothing. Meant to be called in tests, e.g. with
+    /// `assert_eq!(routes(router).validate(), [])`. In debug builds, the
+    /// issues of the whole tree are also logged as warnings once the root
+    /// router is mounted.
+    pub fn validate(&self) -> Vec<Issue> {
+        let parent = self.scope.parent.borrow();
+        let parents: Vec<_> = parent.params.keys().cloned().collect();
+        self.validate_level(&parent.path.to_string(), parents)
+    }
+
+    fn validate_level(&self, prefix: &str, parents: Vec<String>) -> Vec<Issue> {
+        let routes: Vec<_> = self.routes.iter().map(|e| &e.route).collect();
+        let mut issues =
+            validate::check_level(prefix, &routes, self.default_path.as_ref(), &parents);
+
+        for entry in &self.routes {
+            if let Some(children) = &entry.children {
+                let parents = parents
+                    .iter()
+                    .cloned()
+                    .chain(entry.route.parameters().map(str::to_string))
+                    .collect();
+
+                let prefix = validate::join(prefix, &entry.route.to_string());
+                let router = children(self.nested(None));
+
+                issues.extend(router.validate_level(&prefix, parents));
+            }
+        }
+
+        issues
+    }
+
+    /// Resolves this level and all declared levels below, passing the entry,
+    /// the match and the parameters until each level to `visit`. Fails with
+    /// the redirect target of the first level that does not match, if any.
+    fn resolve_level(
+        &self,
+        path: &Path,
+        visit: &mut dyn FnMut(&Entry, &Context, &Params),
+    ) -> Result<Vec<RouteMatch>, Option<Path>> {
+        let Some((i, context)) = self.find(path) else {
+            return Err(self
+                .default_path
+                .as_ref()
+                .map(|d| self.scope.parent.borrow().path.clone() + d.clone()));
+        };
+
+        let entry = &self.routes[i];
+        let params = self
+            .scope
+            .parent
+            .borrow()
+            .clone()
+            .merge_opt(Some(context.clone()))
+            .params;
+
+        visit(entry, &context, &params);
+
+        let level = RouteMatch {
+            route: entry.route.to_string(),
+            path: context.path.to_string(),
+            params: context.params.clone(),
+        };
+
+        let mut chain = match &entry.children {
+            Some(children) => children(self.nested(Some(context))).resolve_level(path, visit)?,
+            None => Vec::new(),
+        };
+
+        chain.insert(0, level);
+        Ok(chain)
+    }
+
+    /// Turns the element into a link, see [`RouterHandle::link`].
+    pub fn link<B>(&self, path: &str) -> impl FnOnce(DomBuilder<B>) -> DomBuilder<B> + '_
+    where
+        B: AsRef<EventTarget> + AsRef<Element> + Clone,
+    {
+        let path = path.to_string();
+        let handle = self.handle();
+
+        move |dom| link(handle, path, LinkOptions::default(), dom)
+    }
+
+    /// The value of a parameter of the current route or of one of its parents.
+    /// When several levels have a parameter with the same name, the deepest
+    /// one wins, see [`Router::param_at`] to access the others.
+    pub fn param(&self, key: &str) -> Option<String> {
+        self.handle().param(key)
+    }
+
+    /// The value of a parameter of a single level, where level 0 is the root
+    /// router and the level of this router is the last one.
+    pub fn param_at(&self, level: usize, key: &str) -> Option<String> {
+        self.handle().param_at(level, key)
+    }
+
+    pub fn signal_active(&self, path: &str) -> impl Signal<Item = bool> {
+        self.handle().signal_active(path)
+    }
+
+    /// Like [`Router::link`], but with custom options for styling the link.
+    pub fn link_with<B>(
+        &self,
+        path: &str,
+        options: LinkOptions,
+    ) -> impl FnOnce(DomBuilder<B>) -> DomBuilder<B> + '_
+    where
+        B: AsRef<EventTarget> + AsRef<Element> + Clone,
+    {
+        let path = path.to_string();
+        let handle = self.handle();
+
+        move |dom| link(handle, path, options, dom)
+    }
+}
+
+#[derive(Clone)]
+pub struct RouterHandle {
+    root: Root,
+    scope: Scope,
+}
+
+impl RouterHandle {
+    /// A handle for the router one level up, e.g. to link to a sibling of the
+    /// current section without counting `..` segments. Returns `None` for the
+    /// root router.
+    pub fn parent(&self) -> Option<RouterHandle> {
+        let level = self.scope.ancestors.len().checked_sub(1)?;
+        Some(self.at_level(level))
+    }
+
+    /// A handle for the root router, whose targets are relative to the base
+    /// path of the app.
+    pub fn root_handle(&self) -> RouterHandle {
+        self.at_level(0)
+    }
+
+    /// A handle for the router at the given level above this one, which
+    /// follows whatever that router matches.
+    fn at_level(&self, level: usize) -> RouterHandle {
+        match self.scope.ancestors.get(level) {
+            Some(scope) => RouterHandle {
+                root: self.root.clone(),
+                scope: scope.clone(),
+            },
+            None => self.clone(),
+        }
+    }
+
+    pub fn goto(&self, target: &str) {
+        self.push(target, None);
+    }
+
+    /// Like [`RouterHandle::goto`], but attaches the given state to the new
+    /// history entry. It is restored when navigating back to the entry, see
+    /// [`RouterHandle::signal_state`].
+    pub fn goto_with_state<S: Serialize>(&self, target: &str, state: &S) {
+        self.push(target, Some(serde_json::to_value(state).unwrap()));
+    }
+
+    pub fn replace(&self, target: &str) {
+        self.overwrite(target, None, Cause::Replace);
+    }
+
+    /// Like [`RouterHandle::replace`], but attaches the given state to the
+    /// current history entry.
+    pub fn replace_with_state<S: Serialize>(&self, target: &str, state: &S) {
+        self.overwrite(
+            target,
+            Some(serde_json::to_value(state).unwrap()),
+            Cause::Replace,
+        );
+    }
+
+    /// A stream of all navigations from now on, e.g. for analytics.
+    pub fn navigations(&self) -> impl Stream<Item = Navigation> {
+        self.root.navigations.subscribe()
+    }
+
+    pub fn back(&self) {
+        self.go(-1);
+    }
+
+    pub fn forward(&self) {
+        self.go(1);
+    }
+
+    /// Moves `delta` entries forward or, for negative values, backward in the
+    /// history.
+    pub fn go(&self, delta: isize) {
+        self.root.history.go(delta);
+    }
+
+    /// Goes back if the previous history entry belongs to the app. Otherwise,
+    /// e.g. after opening a deep link directly, the current entry is replaced
+    /// with the fallback.
+    pub fn back_or(&self, fallback: &str) {
+        if self.can_go_back() {
+            self.back();
+        } else {
+            self.replace(fallback);
+        }
+    }
+
+    /// Whether the previous history entry has been created by the router.
+    pub fn can_go_back(&self) -> bool {
+        self.root.entry.lock_ref().index > 0
+    }
+
+    /// Like [`RouterHandle::can_go_back`], but updates on every navigation.
+    pub fn signal_can_go_back(&self) -> impl Signal<Item = bool> {
+        self.root.entry.signal_ref(|e| e.index > 0).dedupe()
+    }
+
+    /// The state attached to the current history entry, if it can be
+    /// deserialized into `T`.
+    pub fn state<T: DeserializeOwned>(&self) -> Option<T> {
+        self.root
+            .entry
+            .lock_ref()
+            .data
+            .clone()
+            .and_then(|d| serde_json::from_value(d).ok())
+    }
+
+    /// Like [`RouterHandle::state`], but updates whenever the current history
+    /// entry changes.
+    pub fn signal_state<T: DeserializeOwned>(&self) -> impl Signal<Item = Option<T>> {
+        self.root
+            .entry
+            .signal_ref(|e| e.data.clone().and_then(|d| serde_json::from_value(d).ok()))
+    }
+
+    /// The values of the given type attached to the matched routes of all
+    /// mounted routers, from the root down. Routes without such a value are
+    /// skipped.
+    pub fn data<T: Clone + 'static>(&self) -> Vec<T> {
+        self.root.chain.get(|l| l.get::<T>().cloned())
+    }
+
+    /// Like [`RouterHandle::data`], but as a signal, e.g. to render the
+    /// layout of the app depending on the current routes.
+    pub fn signal_data<T: Clone + 'static>(&self) -> impl Signal<Item = Vec<T>> {
+        self.root.chain.signal(|l| l.get::<T>().cloned())
+    }
+
+    /// The matched routes of all mounted routers that have been labeled with
+    /// [`Router::breadcrumb`], from the root down, e.g. to render
+    /// "Projects › Apollo › Settings". As the path of each crumb starts at the
+    /// root router, it can link to its level with
+    /// `handle.root_handle().link(&crumb.path)`.
+    pub fn breadcrumbs(&self) -> impl SignalVec<Item = Breadcrumb> {
+        self.root.chain.signal(Level::breadcrumb).to_signal_vec()
+    }
+
+    /// Whether the latest navigation went forward or back in the history.
+    pub fn direction(&self) -> Direction {
+        self.root.direction.get()
+    }
+
+    pub fn signal_direction(&self) -> impl Signal<Item = Direction> {
+        self.root.direction.signal()
+    }
+
+    /// Asks before leaving the current page while the signal is `true`, e.g.
+    /// while a form has unsaved changes. This covers navigations within the
+    /// app through [`RouterHandle::goto`] and [`RouterHandle::replace`], the
+    /// back and forward buttons, as well as reloading or closing the page. The
+    /// block is lifted once the returned guard is dropped. Use
+    /// [`Router::confirm_with`] to customize the question.
+    pub fn block(&self, signal: impl Signal<Item = bool> + 'static) -> BlockGuard {
+        self.root.blocks.add(signal)
+    }
+
+    fn push(&self, target: &str, data: Option<Value>) {
+        let (target, suffix) = split_url(target);
+        let target = self.link_target(target);
+
+        if !self.root.blocks.allow(&target.to_string()) {
+            return;
+        }
+
+        self.root.persist_scroll();
+        let navigation = self.root.navigate();
+
+        let entry = EntryState {
+            index: self.root.entry.lock_ref().index + 1,
+            data,
+            scroll: None,
+        };
+
+        self.root.scroll.truncate(entry.index);
+
+        // This does not trigger a popstate event, so we need to update the URL
+        // afterwards to keep everything in sync.
+        self.root.history.push(
+            &format!("{}{suffix}", self.root.url(&target)),
+            &entry.encode(),
+        );
+
+        self.root.entry.set(entry);
+        self.root.direction.set_neq(Direction::Forward);
+        self.root.set_path(target, Cause::Push);
+        self.root.navigated(
+            navigation,
+            match fragment(suffix) {
+                Some(f) => Target::Fragment(f.to_string()),
+                None => Target::Top,
+            },
+        );
+    }
+
+    fn overwrite(&self, target: &str, data: Option<Value>, cause: Cause) {
+        let (target, suffix) = split_url(target);
+        let target = self.link_target(target);
+
+        // Redirects only complete a navigation that has already been allowed
+        if cause == Cause::Replace && !self.root.blocks.allow(&target.to_string()) {
+            return;
+        }
+
+        let entry = EntryState {
+            index: self.root.entry.lock_ref().index,
+            data,
+            scroll: None,
+        };
+
+        // This does not trigger a popstate event, so we need to update the URL
+        // afterwards to keep everything in sync.
+        self.root.history.replace(
+            &format!("{}{suffix}", self.root.url(&target)),
+            &entry.encode(),
+        );
+
+        self.root.entry.set(entry);
+
+        // Redirects belong to the navigation that caused them
+        if cause == Cause::Replace {
+            self.root.direction.set_neq(Direction::Forward);
+        }
+
+        self.root.set_path(target, cause);
+    }
+
+    /// Redirects to the `default` route of a router while keeping the state
+    /// of the current navigation.
+    fn redirect(&self, target: &str) {
+        let data = self.root.entry.lock_ref().data.clone();
+        self.overwrite(target, data, Cause::Redirect);
+    }
+
+    /// The value of a parameter, see [`Router::param`].
+    pub fn param(&self, key: &str) -> Option<String> {
+        let current = self.scope.current.borrow();
+        let parent = self.scope.parent.borrow();
+
+        current
+            .as_ref()
+            .and_then(|ctx| ctx.params.get(key))
+            .or_else(|| parent.params.get(key))
+            .cloned()
+    }
+
+    /// The value of a parameter of a single level, see [`Router::param_at`].
+    pub fn param_at(&self, level: usize, key: &str) -> Option<String> {
+        let scope = match self.scope.ancestors.get(level) {
+            Some(scope) => scope,
+            None if level == self.scope.ancestors.len() => &self.scope,
+            None => return None,
+        };
+
+        let current = scope.current.borrow();
+        current.as_ref().and_then(|c| c.params.get(key)).cloned()
+    }
+
+    /// Turns the element into a link to the given path. Anchors get an `href`
+    /// attribute, forms navigate with their fields as query when submitted,
+    /// and all other elements get the `link` role and keyboard support.
+    pub fn link<B>(&self, path: &str) -> impl FnOnce(DomBuilder<B>) -> DomBuilder<B> + '_
+    where
+        B: AsRef<EventTarget> + AsRef<Element> + Clone,
+    {
+        let path = path.to_string();
+        let handle = self.clone();
+
+        move |dom| link(handle, path, LinkOptions::default(), dom)
+    }
+
+    pub fn signal_active(&self, path: &str) -> impl Signal<Item = bool> {
+        self.signal_matches(path, false)
+    }
+
+    /// Like [`RouterHandle::signal_active`], but only when the current path
+    /// does not continue after the given one.
+    pub fn signal_exact(&self, path: &str) -> impl Signal<Item = bool> {
+        self.signal_matches(path, true)
+    }
+
+    pub(crate) fn signal_matches(&self, path: &str, exact: bool) -> impl Signal<Item = bool> {
+        let handle = self.clone();
+        let route: Route = path.parse().unwrap();
+
+        self.root.path.signal_ref(move |p| {
+            let test = p.skip(handle.scope.parent.borrow().path.len());
+
+            route
+                .match_path(&test)
+                .is_some_and(|(m, _)| !exact || m.len() == test.len())
+        })
+    }
+
+    /// Like [`RouterHandle::link`], but with custom options for styling the
+    /// link.
+    pub fn link_with<B>(
+        &self,
+        path: &str,
+        options: LinkOptions,
+    ) -> impl FnOnce(DomBuilder<B>) -> DomBuilder<B>
+    where
+        B: AsRef<EventTarget> + AsRef<Element> + Clone,
+    {
+        let path = path.to_string();
+        let handle = self.clone();
+
+        move |dom| link(handle, path, options, dom)
+    }
+
+    /// Whether any loader of the route tree is still running.
+    pub fn signal_pending(&self) -> impl Signal<Item = bool> {
+        self.root.pending.signal_ref(|n| *n > 0)
+    }
+
+    /// The URL for the given target including the base path and any query or
+    /// fragment, e.g. for the `href` attribute of links.
+    pub fn href(&self, target: &str) -> String {
+        let (target, suffix) = split_url(target);
+        format!("{}{suffix}", self.root.url(&self.link_target(target)))
+    }
+
+    /// Strips the base path from a path of the browser, unless the path lies
+    /// outside of it.
+    pub(crate) fn app_path(&self, path: &str) -> Option<Path> {
+        let path: Path = path.parse().ok()?;
+        path.strip_prefix(&self.root.base.borrow())
+    }
+
+    fn link_target(&self, target: &str) -> Path {
+        self.scope.parent.borrow().path.clone() + target.parse().unwrap()
+    }
+}
+
+#[test]
+fn test_memory_history() {
+    use crate::MemoryHistory;
+    use std::task::Waker;
+
+    let history = MemoryHistory::new("/users/5");
+    let router = Router::with_history(history.clone());
+    let handle = router.handle();
+
+    let mut view = Box::pin(
+        router
+            .route("users/:id", || None::<Dom>)
+            .route("about", || None::<Dom>)
+            .default("about")
+            .mount(),
+    );
+
+    let mut poll = || {
+        let _ = view
+            .as_mut()
+            .poll_change(&mut std::task::Context::from_waker(Waker::noop()));
+    };
+
+    poll();
+    assert_eq!(handle.param("id").as_deref(), Some("5"));
+
+    handle.goto_with_state("/users/7", &"from search");
+    poll();
+    assert_eq!(handle.param("id").as_deref(), Some("7"));
+    assert_eq!(history.location(), "/users/7");
+    assert_eq!(handle.state::<String>().as_deref(), Some("from search"));
+
+    history.back();
+    poll();
+    assert_eq!(handle.param("id").as_deref(), Some("5"));
+    assert_eq!(handle.state::<String>(), None);
+    assert_eq!(handle.direction(), Direction::Back);
+
+    handle.forward();
+    assert_eq!(handle.state::<String>().as_deref(), Some("from search"));
+    assert_eq!(handle.direction(), Direction::Forward);
+    assert!(handle.can_go_back());
+
+    handle.back_or("/about");
+    poll();
+    assert_eq!(handle.param("id").as_deref(), Some("5"));
+
+    handle.back_or("/about");
+    poll();
+    assert_eq!(history.location(), "/about");
+
+    handle.goto("/unknown");
+    poll();
+    assert_eq!(history.location(), "/about");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.index(), 1);
+}
+
+#[test]
+fn test_base() {
+    use crate::MemoryHistory;
+    use std::task::Waker;
+
+    let history = MemoryHistory::new("/tools/planner/users/5");
+    let router = Router::with_history(history.clone()).base("/tools/planner");
+    let handle = router.handle();
+
+    let mut view = Box::pin(
+        router
+            .route("users/:id", || None::<Dom>)
+            .route("about", || None::<Dom>)
+            .mount(),
+    );
+
+    let mut poll = || {
+        let _ = view
+            .as_mut()
+            .poll_change(&mut std::task::Context::from_waker(Waker::noop()));
+    };
+
+    poll();
+    assert_eq!(handle.param("id").as_deref(), Some("5"));
+    assert_eq!(
+        handle.href("/about?tab=1#top"),
+        "/tools/planner/about?tab=1#top"
+    );
+
+    handle.goto("/users/7");
+    poll();
+    assert_eq!(handle.param("id").as_deref(), Some("7"));
+    assert_eq!(history.location(), "/tools/planner/users/7");
+
+    history.back();
+    poll();
+    assert_eq!(handle.param("id").as_deref(), Some("5"));
+}
+
+#[test]
+fn test_untracked_entries() {
+    use crate::MemoryHistory;
+
+    let history = MemoryHistory::new("/a");
+    let router = Router::with_history(history.clone());
+    let handle = router.handle();
+
+    // Like following a link to a fragment, which adds an entry without state
+    History::push(&history, "/a#x", "");
+    history.back();
+    history.forward();
+
+    assert_eq!(handle.direction(), Direction::Forward);
+    assert_eq!(router.root.entry.lock_ref().index, 1);
+    assert!(handle.can_go_back());
+
+    history.back();
+    assert_eq!(handle.direction(), Direction::Back);
+    assert!(!handle.can_go_back());
+
+    history.forward();
+    assert_eq!(handle.direction(), Direction::Forward);
+    assert_eq!(router.root.entry.lock_ref().index, 1);
+}
+
+#[test]
+fn test_navigations() {
+    use crate::MemoryHistory;
+    use std::task::{Poll, Waker};
+
+    let history = MemoryHistory::new("/");
+    let router = Router::with_history(history.clone());
+    let handle = router.handle();
+    let mut navigations = Box::pin(router.navigations());
+    let mut cx = std::task::Context::from_waker(Waker::noop());
+
+    let mut view = Box::pin(
+        router
+            .route("users/:id", || None::<Dom>)
+            .route("about", || None::<Dom>)
+            .default("about")
+            .mount(),
+    );
+
+    let _ = view.as_mut().poll_change(&mut cx);
+    handle.goto("/users/5");
+    let _ = view.as_mut().poll_change(&mut cx);
+    history.back();
+    let _ = view.as_mut().poll_change(&mut cx);
+
+    let mut events = Vec::new();
+
+    while let Poll::Ready(Some(n)) = navigations.as_mut().poll_next(&mut cx) {
+        events.push((n.from, n.to, n.cause, n.params.get("id").cloned()));
+    }
+
+    assert_eq!(
+        events,
+        vec![
+            ("/".into(), "/about".into(), Cause::Redirect, None),
+            (
+                "/about".into(),
+                "/users/5".into(),
+                Cause::Push,
+                Some("5".into())
+            ),
+            ("/users/5".into(), "/about".into(), Cause::Pop, None),
+        ]
+    );
+}
+
+#[test]
+fn test_block() {
+    use crate::MemoryHistory;
+
+    let history = MemoryHistory::new("/a");
+    let confirm = Rc::new(Cell::new(false));
+
+    let router = Router::with_history(history.clone()).confirm_with({
+        let confirm = confirm.clone();
+        move |_| confirm.get()
+    });
+
+    let handle = router.handle();
+    let dirty = Mutable::new(false);
+    let guard = handle.block(dirty.signal());
+
+    handle.goto("/b");
+    assert_eq!(history.location(), "/b");
+
+    dirty.set(true);
+    handle.goto("/c");
+    assert_eq!(history.location(), "/b");
+
+    // Navigations from the outside are undone
+    history.back();
+    assert_eq!(history.location(), "/b");
+    assert_eq!(router.root.current_path().to_string(), "/b");
+
+    History::push(&history, "/b#x", "");
+    history.back();
+    history.forward();
+    assert_eq!(history.location(), "/b");
+    assert_eq!(router.root.current_path().to_string(), "/b");
+
+    // Another URL with the same index, e.g. when a script has copied the state
+    let state = history.state().unwrap();
+    History::push(&history, "/e", &state);
+    history.back();
+    history.forward();
+    assert_eq!(history.location(), "/b");
+    assert_eq!(router.root.current_path().to_string(), "/b");
+
+    confirm.set(true);
+    handle.replace("/c");
+    assert_eq!(history.location(), "/c");
+
+    confirm.set(false);
+    drop(guard);
+    handle.goto("/d");
+    assert_eq!(history.location(), "/d");
+}
+
+/// The mounted routers of a test, by their depth, which are polled together
+/// like the outlets of an app.
+#[cfg(test)]
+#[derive(Clone, Default)]
+struct Outlets(Rc<RefCell<Vec<Rc<Outlet>>>>);
+
+#[cfg(test)]
+struct Outlet {
+    view: RefCell<std::pin::Pin<Box<dyn Signal<Item = Option<Dom>>>>>,
+    handle: RouterHandle,
+}
+
+#[cfg(test)]
+impl Outlets {
+    /// Mounts the router, which replaces the one at its depth and all below.
+    fn mount(&self, router: Router) {
+        let mut outlets = self.0.borrow_mut();
+        outlets.truncate(router.depth);
+
+        outlets.push(Rc::new(Outlet {
+            handle: router.handle(),
+            view: RefCell::new(Box::pin(router.mount())),
+        }));
+    }
+
+    /// The handle of the router at the given depth.
+    fn handle(&self, depth: usize) -> RouterHandle {
+        self.0.borrow()[depth].handle.clone()
+    }
+
+    fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    /// Polls all outlets from the root down, including the ones that are
+    /// mounted meanwhile.
+    fn poll(&self) {
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+
+        for depth in 0.. {
+            let Some(outlet) = self.0.borrow().get(depth).cloned() else {
+                break;
+            };
+
+            let _ = outlet.view.borrow_mut().as_mut().poll_change(&mut cx);
+        }
+    }
+}
+
+#[test]
+fn test_loaders() {
+    use crate::MemoryHistory;
+
+    let outlets = Outlets::default();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let user = Mutable::new(false);
+    let posts = Mutable::new(false);
+
+    let loader = |name: &'static str, done: &Mutable<bool>| {
+        let calls = calls.clone();
+        let done = done.clone();
+
+        move |params: Params| {
+            calls.borrow_mut().push(name);
+            let done = done.signal().wait_for(true);
+
+            async move {
+                done.await;
+                params["id"].clone()
+            }
+        }
+    };
+
+    let children = {
+        let loader = loader("posts", &posts);
+        move |router: Router| {
+            router.route_with_loader("posts", loader.clone(), |_: String| None::<Dom>)
+        }
+    };
+
+    let router = Router::with_history(MemoryHistory::new("/"));
+    let handle = router.handle();
+
+    outlets.mount(
+        router
+            .route_with_loader("users/:id", loader("user", &user), {
+                let outlets = outlets.clone();
+
+                move |router: Router, _: String| {
+                    outlets.mount(router);
+                    None::<Dom>
+                }
+            })
+            .children(children),
+    );
+
+    outlets.poll();
+    assert_eq!(handle.root.pending.get(), 0);
+
+    // Both loaders start right away, although the nested router does not
+    // exist until the loader of its parent has finished
+    handle.goto("/users/5/posts");
+    outlets.poll();
+    assert_eq!(*calls.borrow(), ["user", "posts"]);
+    assert_eq!(outlets.len(), 1);
+    assert_eq!(handle.root.pending.get(), 2);
+
+    user.set(true);
+    outlets.poll();
+    assert_eq!(outlets.len(), 2);
+    assert_eq!(handle.root.pending.get(), 1);
+
+    // The nested router takes over the running loader
+    posts.set(true);
+    outlets.poll();
+    assert_eq!(*calls.borrow(), ["user", "posts"]);
+    assert_eq!(handle.root.pending.get(), 0);
+
+    // Levels that keep their match also keep their data
+    handle.goto("/users/5/posts");
+    outlets.poll();
+    assert_eq!(*calls.borrow(), ["user", "posts"]);
+    assert_eq!(handle.root.pending.get(), 0);
+}
+
+#[test]
+fn test_data() {
+    use crate::MemoryHistory;
+    use std::task::Waker;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Layout(&'static str);
+
+    let history = MemoryHistory::new("/users/5");
+    let router = Router::with_history(history);
+    let handle = router.handle();
+
+    let mut view = Box::pin(
+        router
+            .route("users/:id", || None::<Dom>)
+            .data(Layout("sidebar"))
+            .data("users")
+            .breadcrumb_with(|p| format!("User {}", p["id"]))
+            .route("about", || None::<Dom>)
+            .mount(),
+    );
+
+    let mut poll = || {
+        let _ = view
+            .as_mut()
+            .poll_change(&mut std::task::Context::from_waker(Waker::noop()));
+    };
+
+    poll();
+    assert_eq!(handle.data::<Layout>(), [Layout("sidebar")]);
+    assert_eq!(handle.data::<&str>(), ["users"]);
+
+    let breadcrumbs = handle.root.chain.get(Level::breadcrumb);
+    assert_eq!(breadcrumbs.len(), 1);
+    assert_eq!(breadcrumbs[0].label, "User 5");
+    assert_eq!(breadcrumbs[0].path, "/users/5");
+
+    handle.goto("/about");
+    poll();
+    assert_eq!(handle.data::<Layout>(), []);
+}
+
+#[test]
+fn test_nested_params() {
+    use crate::MemoryHistory;
+
+    let outlets = Outlets::default();
+    let router = Router::with_history(MemoryHistory::new("/users/5/posts"));
+    let handle = router.handle();
+
+    outlets.mount(router.route("users/:id", {
+        let outlets = outlets.clone();
+
+        move |router: Router| {
+            outlets.mount(router.route("posts", || None::<Dom>));
+            None::<Dom>
+        }
+    }));
+
+    let nested = || outlets.handle(1);
+
+    outlets.poll();
+    assert_eq!(nested().param("id").as_deref(), Some("5"));
+    assert_eq!(nested().param_at(0, "id").as_deref(), Some("5"));
+    assert_eq!(nested().param_at(1, "id"), None);
+
+    // The nested router is kept, but follows the parameters of its parent
+    let before = nested();
+    handle.goto("/users/7/posts");
+    outlets.poll();
+    assert!(Rc::ptr_eq(&before.scope.current, &nested().scope.current));
+    assert_eq!(nested().param("id").as_deref(), Some("7"));
+    assert_eq!(nested().param_at(0, "id").as_deref(), Some("7"));
+    assert_eq!(nested().href("comments"), "/users/7/comments");
+}
+
+#[test]
+fn test_nested_breadcrumbs() {
+    use crate::MemoryHistory;
+
+    let outlets = Outlets::default();
+    let router = Router::with_history(MemoryHistory::new("/users/5/posts"));
+    let handle = router.handle();
+
+    outlets.mount(
+        router
+            .route("users/:id", {
+                let outlets = outlets.clone();
+
+                move |router: Router| {
+                    outlets.mount(
+                        router
+                            .route("posts", || None::<Dom>)
+                            .breadcrumb_with(|p| format!("Posts of {}", p["id"])),
+                    );
+
+                    None::<Dom>
+                }
+            })
+            .breadcrumb_with(|p| format!("User {}", p["id"])),
+    );
+
+    let breadcrumbs = || {
+        handle
+            .root
+            .chain
+            .get(Level::breadcrumb)
+            .into_iter()
+            .map(|b| (b.label, b.path))
+            .collect::<Vec<_>>()
+    };
+
+    outlets.poll();
+    assert_eq!(
+        breadcrumbs(),
+        [
+            ("User 5".to_string(), "/users/5".to_string()),
+            ("Posts of 5".to_string(), "/users/5/posts".to_string()),
+        ]
+    );
+
+    handle.goto("/users/7/posts");
+    outlets.poll();
+    assert_eq!(
+        breadcrumbs(),
+        [
+            ("User 7".to_string(), "/users/7".to_string()),
+            ("Posts of 7".to_string(), "/users/7/posts".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_parent_handles() {
+    use crate::MemoryHistory;
+
+    let outlets = Outlets::default();
+    let router = Router::with_history(MemoryHistory::new("/teams/1/users/7/posts"));
+    let handle = router.handle();
+
+    outlets.mount(
+        router
+            .route("teams/:team", {
+                let outlets = outlets.clone();
+
+                move |router: Router| {
+                    let nested = outlets.clone();
+
+                    outlets.mount(router.route("users/:id", move |router: Router| {
+                        nested.mount(router.route("posts", || None::<Dom>));
+                        None::<Dom>
+                    }));
+
+                    None::<Dom>
+                }
+            })
+            .route("about", || None::<Dom>),
+    );
+
+    outlets.poll();
+    let posts = outlets.handle(2);
+    assert_eq!(posts.href("comments"), "/teams/1/users/7/comments");
+
+    let parent = posts.parent().unwrap();
+    assert_eq!(parent.href("users/8"), "/teams/1/users/8");
+    assert_eq!(parent.param("id").as_deref(), Some("7"));
+    assert_eq!(parent.param("team").as_deref(), Some("1"));
+
+    assert_eq!(parent.parent().unwrap().href("about"), "/about");
+    assert_eq!(posts.root_handle().href("about"), "/about");
+    assert_eq!(posts.root_handle().param("team").as_deref(), Some("1"));
+    assert!(handle.parent().is_none());
+
+    // Handles of the levels above follow what those levels match
+    handle.goto("/teams/2/users/9/posts");
+    outlets.poll();
+    assert_eq!(parent.param("id").as_deref(), Some("9"));
+    assert_eq!(parent.param("team").as_deref(), Some("2"));
+    assert_eq!(parent.href("users/8"), "/teams/2/users/8");
+    assert_eq!(posts.root_handle().param("team").as_deref(), Some("2"));
 }
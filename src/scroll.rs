@@ -0,0 +1,84 @@
+use gloo::utils::{document, window};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+/// Where the page should be scrolled to once a navigation has been rendered.
+pub(crate) enum Target {
+    Top,
+    Fragment(String),
+    Restore,
+}
+
+/// Keeps track of the scroll offsets of all history entries created by the
+/// router. Entries are identified by their index within the history. Offsets
+/// that have been stored in the state of an entry are used when the entry is
+/// not known, e.g. after a reload.
+#[derive(Default)]
+pub(crate) struct Scroll {
+    positions: RefCell<HashMap<usize, (f64, f64)>>,
+    preserve: Cell<bool>,
+}
+
+impl Scroll {
+    /// Starts a new navigation.
+    pub fn reset(&self) {
+        self.preserve.set(false);
+    }
+
+    /// Forgets the offsets of all entries from the given one on, since they
+    /// are no longer reachable after pushing a new entry.
+    pub fn truncate(&self, index: usize) {
+        self.positions.borrow_mut().retain(|i, _| *i < index);
+    }
+
+    /// Skips scrolling for the current navigation.
+    pub fn preserve(&self) {
+        self.preserve.set(true);
+    }
+
+    /// Scrolls to the given target. Needs to be called after the new views
+    /// have been rendered.
+    pub fn apply(&self, target: Target, index: usize, stored: Option<(f64, f64)>) {
+        if self.preserve.get() {
+            return;
+        }
+
+        match target {
+            Target::Top => {
+                window().scroll_to_with_x_and_y(0.0, 0.0);
+            }
+            Target::Fragment(id) => match document().get_element_by_id(&id) {
+                Some(element) => element.scroll_into_view(),
+                None => window().scroll_to_with_x_and_y(0.0, 0.0),
+            },
+            Target::Restore => {
+                let (x, y) = self
+                    .positions
+                    .borrow()
+                    .get(&index)
+                    .copied()
+                    .or(stored)
+                    .unwrap_or_default();
+
+                window().scroll_to_with_x_and_y(x, y);
+            }
+        }
+    }
+
+    /// Remembers the scroll offset of an entry before leaving it.
+    pub fn save(&self, index: usize) {
+        self.positions.borrow_mut().insert(index, offset());
+    }
+}
+
+/// The current scroll offset of the page.
+pub(crate) fn offset() -> (f64, f64) {
+    let window = window();
+
+    (
+        window.scroll_x().unwrap_or_default(),
+        window.scroll_y().unwrap_or_default(),
+    )
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The state the router stores in every history entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct EntryState {
+    /// The position of the entry within the history.
+    pub index: usize,
+    /// The state attached by the application, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    /// The scroll offset of the page when the entry was last left, which
+    /// survives reloads and visits of other sites.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scroll: Option<(f64, f64)>,
+}
+
+impl EntryState {
+    /// Parses the state of a history entry. Returns `None` for entries that
+    /// have not been created by the router, e.g. by following a link to a
+    /// fragment.
+    pub fn parse(state: Option<String>) -> Option<Self> {
+        state.and_then(|s| serde_json::from_str(&s).ok())
+    }
+
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[test]
+fn test_entry_state() {
+    let state = EntryState {
+        index: 3,
+        data: Some(serde_json::json!({ "from": "search" })),
+        scroll: Some((0.0, 120.0)),
+    };
+
+    assert_eq!(EntryState::parse(Some(state.encode())), Some(state));
+    assert_eq!(
+        EntryState::parse(Some("{\"index\":1}".into())).map(|s| s.index),
+        Some(1)
+    );
+    assert_eq!(EntryState::parse(Some("garbage".into())), None);
+    assert_eq!(EntryState::parse(None), None);
+}
@@ -0,0 +1,89 @@
+use dominator::{clone, html, Dom};
+use futures_signals::{
+    signal::{Mutable, Signal, SignalExt},
+    signal_vec::{MutableVec, SignalVecExt},
+};
+use gloo::timers::future::TimeoutFuture;
+use std::{cell::RefCell, rc::Rc};
+
+use crate::navigation::Direction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Enter,
+    Idle,
+    Leave,
+}
+
+/// A view within a transition outlet.
+struct Phase {
+    dom: RefCell<Option<Dom>>,
+    stage: Mutable<Stage>,
+}
+
+/// Renders the views of the signal one after another, but keeps the leaving
+/// view around for `duration` milliseconds. During that time, the entering
+/// view has the class `routinator-enter` and the leaving one has the class
+/// `routinator-leave`. Both also have a class for the current direction,
+/// `routinator-forward` or `routinator-back`.
+pub(crate) fn outlet<S>(views: S, direction: Mutable<Direction>, duration: u32) -> Dom
+where
+    S: Signal<Item = Option<Dom>> + 'static,
+{
+    let phases = MutableVec::<Rc<Phase>>::new();
+    let mut initial = true;
+
+    let task = views.for_each(clone!(phases => move |dom| {
+        let mut phases = phases.lock_mut();
+
+        for phase in phases.iter() {
+            phase.stage.set_neq(Stage::Leave);
+        }
+
+        // The first view is already there when the page is shown
+        let stage = if initial { Stage::Idle } else { Stage::Enter };
+        initial = false;
+
+        phases.push_cloned(Rc::new(Phase {
+            dom: RefCell::new(dom),
+            stage: Mutable::new(stage),
+        }));
+
+        async {}
+    }));
+
+    html!("div", {
+        .style("display", "contents")
+        .future(task)
+        .children_signal_vec(phases.signal_vec_cloned().map(move |phase| {
+            let stage = |s| phase.stage.signal_ref(move |stage| *stage == s);
+            let direction = |d| direction.signal_ref(move |direction| *direction == d);
+
+            html!("div", {
+                .style("display", "contents")
+                .class_signal("routinator-enter", stage(Stage::Enter))
+                .class_signal("routinator-leave", stage(Stage::Leave))
+                .class_signal("routinator-forward", direction(Direction::Forward))
+                .class_signal("routinator-back", direction(Direction::Back))
+                .future(clone!(phase => async move {
+                    if phase.stage.get() == Stage::Enter {
+                        TimeoutFuture::new(duration).await;
+
+                        // Might already be leaving again
+                        let mut stage = phase.stage.lock_mut();
+
+                        if *stage == Stage::Enter {
+                            *stage = Stage::Idle;
+                        }
+                    }
+                }))
+                .future(clone!(phases, phase => async move {
+                    phase.stage.signal_ref(|s| *s == Stage::Leave).wait_for(true).await;
+                    TimeoutFuture::new(duration).await;
+                    phases.lock_mut().retain(|p| !Rc::ptr_eq(p, &phase));
+                }))
+                .children(phase.dom.take())
+            })
+        }))
+    })
+}
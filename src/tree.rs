@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// The declared routes of a router, see [`Router::tree`].
+///
+/// [`Router::tree`]: crate::Router::tree
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RouteTree {
+    /// The `default` route of the router, as it has been registered.
+    pub default: Option<String>,
+    pub routes: Vec<RouteInfo>,
+}
+
+impl RouteTree {
+    /// All routes of the tree, each followed by its nested routes.
+    pub fn iter(&self) -> impl Iterator<Item = &RouteInfo> {
+        let mut routes = Vec::new();
+        let mut stack: Vec<_> = self.routes.iter().rev().collect();
+
+        while let Some(route) = stack.pop() {
+            routes.push(route);
+
+            if let Some(children) = &route.children {
+                stack.extend(children.routes.iter().rev());
+            }
+        }
+
+        routes.into_iter()
+    }
+}
+
+/// A single route of a [`RouteTree`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteInfo {
+    /// The route as it has been registered, e.g. `users/:id`.
+    pub route: String,
+    /// The absolute path pattern of the route including all of its parents,
+    /// e.g. `/teams/:team/users/:id`.
+    pub path: String,
+    /// The names of the parameters of this level.
+    pub params: Vec<String>,
+    pub loader: bool,
+    pub keep_alive: bool,
+    /// The nested routes, if they have been declared with
+    /// [`Router::children`].
+    ///
+    /// [`Router::children`]: crate::Router::children
+    pub children: Option<RouteTree>,
+}
+
+impl RouteInfo {
+    /// Whether the path has no parameters on any level, so it can be linked
+    /// to as it is, e.g. from a sitemap.
+    pub fn is_static(&self) -> bool {
+        !self.path.split('/').any(|s| s.starts_with(':'))
+    }
+}
+
+#[test]
+fn test_tree() {
+    use crate::{MemoryHistory, Router};
+
+    fn user(router: Router) -> Router {
+        router
+            .route("settings", || None)
+            .route("posts/:post", || None)
+            .default("settings")
+    }
+
+    let router = Router::with_history(MemoryHistory::default())
+        .route("users/:id", || None)
+        .children(user)
+        .route("about", || None)
+        .keep_alive();
+
+    let tree = router.tree();
+    assert_eq!(router.validate(), []);
+
+    let paths: Vec<_> = tree.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "/users/:id",
+            "/users/:id/settings",
+            "/users/:id/posts/:post",
+            "/about"
+        ]
+    );
+
+    let about = tree.iter().last().unwrap();
+    assert!(about.is_static() && about.keep_alive);
+    assert!(!tree.routes[0].is_static());
+    assert_eq!(tree.routes[0].params, ["id"]);
+
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(serde_json::from_str::<RouteTree>(&json).unwrap(), tree);
+    assert_eq!(
+        tree.routes[0].children.as_ref().unwrap().default.as_deref(),
+        Some("settings")
+    );
+}
@@ -0,0 +1,168 @@
+use std::fmt::Display;
+
+use crate::path::{Path, Route, Segment};
+
+/// A problem with the routes of a router, see [`Router::validate`].
+///
+/// Routes are given as absolute path patterns including all of their
+/// parents, e.g. `/users/:id/settings`.
+///
+/// [`Router::validate`]: crate::Router::validate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// An earlier route matches every path of a later one, since routes are
+    /// matched by prefix, so the later route is never used.
+    Shadowed { route: String, by: String },
+    /// Two routes have the same pattern, apart from the names of their
+    /// parameters.
+    Duplicate { route: String, first: String },
+    /// A route has a parameter with the same name as one of its parents,
+    /// which hides the value of the parent.
+    ParameterClash { route: String, parameter: String },
+    /// No route of the router matches its `default` route.
+    UnmatchedDefault { router: String, default: String },
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Shadowed { route, by } => {
+                write!(
+                    f,
+                    "route `{route}` is unreachable, since `{by}` matches first"
+                )
+            }
+            Issue::Duplicate { route, first } => {
+                write!(f, "route `{route}` duplicates `{first}`")
+            }
+            Issue::ParameterClash { route, parameter } => {
+                write!(
+                    f,
+                    "parameter `{parameter}` of route `{route}` hides the one of a parent"
+                )
+            }
+            Issue::UnmatchedDefault { router, default } => {
+                write!(
+                    f,
+                    "default `{default}` of the router at `{router}` matches no route"
+                )
+            }
+        }
+    }
+}
+
+/// Joins the path pattern of a router with one of its routes.
+pub(crate) fn join(prefix: &str, route: &str) -> String {
+    format!("{}/{route}", prefix.trim_end_matches('/'))
+}
+
+/// Checks the routes of a single router, whose path pattern is `prefix` and
+/// whose parents have the given parameters.
+pub(crate) fn check_level(
+    prefix: &str,
+    routes: &[&Route],
+    default: Option<&Path>,
+    parents: &[String],
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for (i, route) in routes.iter().enumerate() {
+        let path = join(prefix, &route.to_string());
+
+        if let Some(earlier) = routes[..i].iter().find(|r| covers(r, route)) {
+            let by = join(prefix, &earlier.to_string());
+
+            issues.push(if covers(route, earlier) {
+                Issue::Duplicate {
+                    route: path.clone(),
+                    first: by,
+                }
+            } else {
+                Issue::Shadowed {
+                    route: path.clone(),
+                    by,
+                }
+            });
+        }
+
+        for parameter in route
+            .parameters()
+            .filter(|p| parents.iter().any(|a| a == p))
+        {
+            issues.push(Issue::ParameterClash {
+                route: path.clone(),
+                parameter: parameter.to_string(),
+            });
+        }
+    }
+
+    // Relative targets depend on the current path
+    if let Some(default) = default.filter(|d| d.is_absolute()) {
+        if !routes.iter().any(|r| r.match_path(default).is_some()) {
+            issues.push(Issue::UnmatchedDefault {
+                router: prefix.to_string(),
+                default: default.to_string().trim_start_matches('/').to_string(),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Whether every path matched by `b` is also matched by `a`.
+fn covers(a: &Route, b: &Route) -> bool {
+    a.len() <= b.len()
+        && a.segments().iter().zip(b.segments()).all(|s| match s {
+            (Segment::Parameter(_), _) => true,
+            (Segment::Static(a), Segment::Static(b)) => a == b,
+            _ => false,
+        })
+}
+
+#[test]
+fn test_check_level() {
+    let routes: Vec<Route> = [
+        "users",
+        "users/:id",
+        "posts/:id",
+        "posts/:post",
+        "posts/new",
+    ]
+    .iter()
+    .map(|r| r.parse().unwrap())
+    .collect();
+
+    let routes: Vec<_> = routes.iter().collect();
+    let default = "about".parse().unwrap();
+    let issues = check_level("/app", &routes, Some(&default), &["id".to_string()]);
+
+    assert_eq!(
+        issues,
+        [
+            Issue::Shadowed {
+                route: "/app/users/:id".to_string(),
+                by: "/app/users".to_string(),
+            },
+            Issue::ParameterClash {
+                route: "/app/users/:id".to_string(),
+                parameter: "id".to_string(),
+            },
+            Issue::ParameterClash {
+                route: "/app/posts/:id".to_string(),
+                parameter: "id".to_string(),
+            },
+            Issue::Duplicate {
+                route: "/app/posts/:post".to_string(),
+                first: "/app/posts/:id".to_string(),
+            },
+            Issue::Shadowed {
+                route: "/app/posts/new".to_string(),
+                by: "/app/posts/:id".to_string(),
+            },
+            Issue::UnmatchedDefault {
+                router: "/app".to_string(),
+                default: "about".to_string(),
+            },
+        ]
+    );
+}
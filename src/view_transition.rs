@@ -0,0 +1,104 @@
+use discard::Discard;
+use dominator::{append_dom, clone, html, with_node, Dom, DomBuilder, DomHandle};
+use futures_signals::signal::{Mutable, Signal, SignalExt};
+use gloo::utils::document;
+use std::{cell::RefCell, rc::Rc};
+use web_sys::{
+    js_sys::{Function, Reflect},
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+    Element, HtmlElement,
+};
+
+use crate::router::Pending;
+
+/// Gives the element a `view-transition-name`, so the browser animates it
+/// from its old to its new position when switching to a view that contains
+/// an element with the same name. Names need to be unique within a view.
+/// See [`Router::view_transitions`].
+///
+/// [`Router::view_transitions`]: crate::Router::view_transitions
+pub fn shared_element<A>(name: &str) -> impl FnOnce(DomBuilder<A>) -> DomBuilder<A> + '_
+where
+    A: AsRef<HtmlElement>,
+{
+    move |dom| dom.style("view-transition-name", name)
+}
+
+/// Renders the views of the signal, but swaps them within a view transition
+/// of the document when the browser supports it. The swap counts as a pending
+/// navigation until the browser has taken its snapshot of the old view.
+pub(crate) fn outlet<S>(views: S, pending: Mutable<usize>) -> Dom
+where
+    S: Signal<Item = Option<Dom>> + 'static,
+{
+    let current = Rc::new(RefCell::new(None::<DomHandle>));
+
+    html!("div", {
+        .style("display", "contents")
+        .with_node!(element => {
+            .future(views.for_each(clone!(current => {
+                let mut initial = true;
+
+                move |dom| {
+                    let update = clone!(element, current => move || swap(&element, &current, dom));
+
+                    // The first view is already there when the page is shown
+                    if initial {
+                        update();
+                    } else {
+                        let pending = Pending::new(&pending);
+
+                        start_view_transition(move || {
+                            update();
+                            drop(pending);
+                        });
+                    }
+
+                    initial = false;
+                    async {}
+                }
+            })))
+        })
+        .after_removed(move |_| {
+            if let Some(handle) = current.take() {
+                handle.discard();
+            }
+        })
+    })
+}
+
+fn swap(parent: &Element, current: &RefCell<Option<DomHandle>>, dom: Option<Dom>) {
+    if let Some(handle) = current.take() {
+        handle.discard();
+    }
+
+    if let Some(dom) = dom {
+        current.replace(Some(append_dom(parent, dom)));
+    }
+}
+
+/// Calls `document.startViewTransition` with the update, or just runs the
+/// update when view transitions are not supported.
+fn start_view_transition(update: impl FnOnce() + 'static) {
+    let document = document();
+    let update = Rc::new(RefCell::new(Some(update)));
+
+    let started = Reflect::get(&document, &JsValue::from_str("startViewTransition"))
+        .ok()
+        .and_then(|f| f.dyn_into::<Function>().ok())
+        .is_some_and(|start| {
+            let callback = Closure::once_into_js(clone!(update => move || {
+                if let Some(update) = update.take() {
+                    update();
+                }
+            }));
+
+            start.call1(&document, &callback).is_ok()
+        });
+
+    if !started {
+        if let Some(update) = update.take() {
+            update();
+        }
+    }
+}